@@ -0,0 +1,56 @@
+use crate::{Contract, ContractExt, Event};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Promise};
+use primitive_types::U256;
+
+#[near_bindgen]
+impl Contract {
+    /// Sends the dust of `token_id`, that was left over after all shares have been burnt, to the owner.
+    pub fn sweep_dust(&mut self, token_id: AccountId) -> Promise {
+        self.require_owner();
+        self.internal_collect_dust();
+        let amount = self
+            .dust
+            .remove(&token_id)
+            .unwrap_or_else(|| env::panic_str("No dust found for token"));
+
+        Event::DustSwept {
+            token_id: token_id.clone(),
+            amount: U128(amount),
+        }
+        .emit();
+        Self::payout_transfer(token_id, self.owner.clone(), amount)
+    }
+}
+
+impl Contract {
+    /// Returns the part of `deposit` that belongs to `shares`.
+    /// Burning the whole supply returns the full deposit including all rounding remainders.
+    pub(crate) fn share_of(&self, shares: u128, deposit: u128) -> u128 {
+        if self.shares == 0 {
+            0
+        } else if shares >= self.shares {
+            deposit
+        } else {
+            (U256::from(shares) * U256::from(deposit) / U256::from(self.shares)).as_u128()
+        }
+    }
+
+    /// Moves all rewards to the dust, once there are no shares left that can redeem them.
+    pub(crate) fn internal_collect_dust(&mut self) {
+        if self.shares > 0 {
+            return;
+        }
+        for (token_id, amount) in self.rewards.drain() {
+            if amount == 0 {
+                continue;
+            }
+            *self.dust.entry(token_id.clone()).or_default() += amount;
+
+            Event::DustCollected {
+                token_id,
+                amount: U128(amount),
+            }
+            .emit();
+        }
+    }
+}
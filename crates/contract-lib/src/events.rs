@@ -0,0 +1,161 @@
+use crate::{
+    HolderProtection, PausableFeature, PayoutSource, PenaltyDestination, Role, TokenState,
+    TransferPolicy,
+};
+use near_sdk::{
+    json_types::{Base58CryptoHash, U128, U64},
+    near_bindgen, AccountId,
+};
+
+#[near_bindgen(event_json(standard = "validator-rewards"))]
+pub enum Event {
+    #[event_version("1.0.0")]
+    TokenWhitelisted { token_id: AccountId },
+    #[event_version("1.0.0")]
+    TokenUnwhitelisted { token_id: AccountId },
+    #[event_version("1.0.0")]
+    TokenStateUpdated {
+        token_id: AccountId,
+        state: TokenState,
+    },
+    #[event_version("1.0.0")]
+    TokenMetadataUpdated {
+        token_id: AccountId,
+        symbol: String,
+        decimals: u8,
+    },
+    #[event_version("1.0.0")]
+    TokenDeposited {
+        token_id: AccountId,
+        sender_id: AccountId,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    SponsorAdded { account_id: AccountId },
+    #[event_version("1.0.0")]
+    SponsorRemoved { account_id: AccountId },
+    #[event_version("1.0.0")]
+    SponsorDepositRefunded {
+        sponsor_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    DepositWithdrawn {
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    RewardAdded { token_id: AccountId, amount: U128 },
+    #[event_version("1.0.0")]
+    MintPriced {
+        shares: U128,
+        share_values: Vec<(AccountId, U128)>,
+    },
+    #[event_version("1.0.0")]
+    RewardWithdrawn {
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    HolderProtectionUpdated { protection: HolderProtection },
+    #[event_version("1.0.0")]
+    WithdrawalDelayUpdated { delay: U64 },
+    #[event_version("1.0.0")]
+    RewardWithdrawalAnnounced {
+        token_id: AccountId,
+        amount: U128,
+        executable_at: U64,
+    },
+    #[event_version("1.0.0")]
+    RewardWithdrawalCancelled { token_id: AccountId, amount: U128 },
+    #[event_version("1.0.0")]
+    RewardRemoved {
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    OwnerPayoutFailed {
+        source: PayoutSource,
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    BurnPayout {
+        account_id: AccountId,
+        receiver_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    },
+    #[event_version("1.0.0")]
+    PenaltyApplied {
+        account_id: AccountId,
+        amount: U128,
+        destination: PenaltyDestination,
+    },
+    #[event_version("1.0.0")]
+    PenaltyUpdated {
+        rate_bps: u16,
+        destination: PenaltyDestination,
+    },
+    #[event_version("1.0.0")]
+    ScoreMultiplierUpdated {
+        token_id: AccountId,
+        multiplier_bps: Option<u32>,
+    },
+    #[event_version("1.0.0")]
+    RoleGranted { account_id: AccountId, role: Role },
+    #[event_version("1.0.0")]
+    RoleRevoked { account_id: AccountId, role: Role },
+    #[event_version("1.0.0")]
+    Paused { feature: PausableFeature },
+    #[event_version("1.0.0")]
+    Unpaused { feature: PausableFeature },
+    #[event_version("1.0.0")]
+    OwnerProposed { new_owner_id: AccountId },
+    #[event_version("1.0.0")]
+    OwnerTransferred {
+        old_owner_id: AccountId,
+        new_owner_id: AccountId,
+    },
+    #[event_version("1.0.0")]
+    ValidatorUpdated {
+        old_validator_id: AccountId,
+        new_validator_id: AccountId,
+    },
+    #[event_version("1.0.0")]
+    RewarderUpdated {
+        old_rewarder_id: AccountId,
+        new_rewarder_id: AccountId,
+    },
+    #[event_version("1.0.0")]
+    ShitzuNftUpdated {
+        old_nft_id: AccountId,
+        new_nft_id: AccountId,
+    },
+    #[event_version("1.0.0")]
+    UpgradeStaged {
+        code_hash: Base58CryptoHash,
+        executable_at: U64,
+    },
+    #[event_version("1.0.0")]
+    UpgradeCancelled { code_hash: Base58CryptoHash },
+    #[event_version("1.0.0")]
+    UpgradeExecuted { code_hash: Base58CryptoHash },
+    #[event_version("1.0.0")]
+    UpgradeDelayUpdated { delay: U64 },
+    #[event_version("1.0.0")]
+    TransferPolicyUpdated { policy: TransferPolicy },
+    #[event_version("1.0.0")]
+    TransferAllowlistAdded { account_id: AccountId },
+    #[event_version("1.0.0")]
+    TransferAllowlistRemoved { account_id: AccountId },
+    #[event_version("1.0.0")]
+    DustCollected { token_id: AccountId, amount: U128 },
+    #[event_version("1.0.0")]
+    DustSwept { token_id: AccountId, amount: U128 },
+}
@@ -1,9 +1,35 @@
+mod dust;
+mod events;
+mod migrate;
+mod mint;
 mod owner;
+mod pause;
+mod payout;
+mod protection;
+mod role;
+mod snapshot;
+mod sponsor;
+mod token;
+mod transfer;
+mod upgrade;
 mod view;
 
+pub use events::Event;
+pub use migrate::{HolderIndex, STATE_VERSION};
+pub use mint::{MintMode, MintResult};
+pub use pause::{PausableFeature, PauseState};
+pub use payout::PayoutSource;
+pub use protection::{AnnouncedWithdrawal, HolderProtection, ProtectedWithdrawal};
+pub use role::Role;
+pub use snapshot::MintSnapshot;
+pub use sponsor::SponsorContribution;
+pub use token::{TokenInfo, TokenState};
+pub use transfer::TransferPolicy;
+pub use upgrade::{StagedUpgrade, MIN_UPGRADE_DELAY};
+pub use view::{BurnSimulation, MintSimulation, ShareValueChange};
+
 use near_contract_standards::{
     fungible_token::{
-        core::ext_ft_core,
         events::{FtBurn, FtTransfer},
         metadata::{FungibleTokenMetadata, FungibleTokenMetadataProvider},
         receiver::{ext_ft_receiver, FungibleTokenReceiver},
@@ -19,18 +45,32 @@ use near_sdk::{
     borsh::{BorshDeserialize, BorshSerialize},
     env, ext_contract,
     json_types::U128,
-    near_bindgen, require, serde_json,
-    store::{Lazy, TreeMap},
-    AccountId, BorshStorageKey, Gas, NearToken, PanicOnDefault, PromiseOrValue, PromiseResult,
+    log, near, near_bindgen, require, serde_json,
+    store::{IterableMap, IterableSet, LookupMap, TreeMap, Vector},
+    AccountId, BorshStorageKey, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue,
+    PromiseResult, StorageUsage,
 };
 use primitive_types::U256;
-use std::cmp;
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+};
 
 const GAS_FOR_BURN: Gas = Gas::from_tgas(5);
 const GAS_FOR_NFT_CHECK: Gas = Gas::from_tgas(5);
 const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
 const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas::from_tgas(60);
 const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas::from_tgas(5);
+const GAS_FOR_RESOLVE_PAYOUT: Gas = Gas::from_tgas(5);
+const GAS_FOR_RESOLVE_OWNER_PAYOUT: Gas = Gas::from_tgas(15);
+const GAS_FOR_MIGRATE: Gas = Gas::from_tgas(50);
+const GAS_FOR_FT_METADATA: Gas = Gas::from_tgas(5);
+const GAS_FOR_RESOLVE_METADATA: Gas = Gas::from_tgas(5);
+
+const BPS_DIVISOR: u16 = 10_000;
+const SHARE_DECIMALS: u8 = 24;
+const ONE_SHARE: u128 = 10u128.pow(SHARE_DECIMALS as u32);
+const DEFAULT_SHITZU_SCORE_MULTIPLIER: u32 = 30_000;
 
 #[ext_contract(shitzu_nft)]
 #[allow(dead_code)]
@@ -52,9 +92,68 @@ pub enum StorageKey {
     Deposits,
     Rewards,
     TokenWhitelist,
+    PendingPayouts,
+    BurntRewards,
+    ScoreMultipliers,
+    Roles,
+    StateVersion,
+    TransferAllowlist,
+    MintSnapshots,
+    Dust,
+    Tokens,
+    Sponsors,
+    SponsorContributions,
+    RewardWithdrawals,
+    Holders,
+}
+
+/// Where the shares go, that are taken as penalty from burners without a primary NFT.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PenaltyDestination {
+    /// Shares are credited to the owner.
+    Owner,
+    /// Shares are burnt together with the wrapped tokens backing them.
+    /// These tokens are locked in the contract and can't be redeemed anymore.
+    Burn,
+    /// Shares are burnt, but the wrapped tokens backing them stay in `rewards`,
+    /// which increases the value of every remaining share.
+    Redistribute,
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug)]
+pub struct PenaltyConfig {
+    pub rate_bps: u16,
+    pub destination: PenaltyDestination,
+}
+
+impl Default for PenaltyConfig {
+    fn default() -> Self {
+        Self {
+            rate_bps: 2_000,
+            destination: PenaltyDestination::Owner,
+        }
+    }
 }
 
-#[near_bindgen(contract_metadata(standard(standard = "nep148", version = "1.0.0")))]
+/// Message of `ft_transfer_call` deposits. An empty message is a plain deposit.
+#[near(serializers = [json])]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum DepositMsg {
+    Deposit,
+    /// Deposits the tokens and mints `shares` in the same transaction.
+    /// Only the owner can deposit and mint.
+    DepositAndMint {
+        shares: U128,
+    },
+}
+
+#[near_bindgen(contract_metadata(
+    standard(standard = "nep141", version = "1.0.0"),
+    standard(standard = "nep145", version = "1.0.0"),
+    standard(standard = "nep148", version = "1.0.0")
+))]
 #[derive(BorshSerialize, BorshDeserialize, PanicOnDefault)]
 #[borsh(crate = "near_sdk::borsh")]
 #[allow(deprecated)]
@@ -65,24 +164,32 @@ pub struct Contract {
     shitzu_token: AccountId,
     shitzu_nft: AccountId,
     accounts: TreeMap<AccountId, u128>,
+    /// Accounts with a positive balance ordered by balance.
+    holders: TreeMap<(u128, AccountId), ()>,
+    holder_index: HolderIndex,
     deposits: UnorderedMap<AccountId, u128>,
     rewards: UnorderedMap<AccountId, u128>,
     shares: u128,
-    token_whitelist: Lazy<Vec<AccountId>>,
-}
-
-#[derive(BorshSerialize, BorshDeserialize, PanicOnDefault)]
-#[borsh(crate = "near_sdk::borsh")]
-#[allow(deprecated)]
-pub struct OldContract {
-    owner: AccountId,
-    validator: AccountId,
-    shitzu_nft: AccountId,
-    accounts: TreeMap<AccountId, u128>,
-    deposits: UnorderedMap<AccountId, u128>,
-    rewards: UnorderedMap<AccountId, u128>,
-    shares: u128,
-    token_whitelist: Lazy<Vec<AccountId>>,
+    tokens: IterableMap<AccountId, TokenInfo>,
+    pending_payouts: LookupMap<AccountId, HashMap<AccountId, u128>>,
+    account_storage_usage: StorageUsage,
+    penalty: PenaltyConfig,
+    burnt_rewards: IterableMap<AccountId, u128>,
+    score_multipliers: IterableMap<AccountId, u32>,
+    roles: IterableMap<AccountId, HashSet<Role>>,
+    paused: PauseState,
+    pending_owner: Option<AccountId>,
+    staged_upgrade: Option<StagedUpgrade>,
+    upgrade_delay: u64,
+    transfer_policy: TransferPolicy,
+    transfer_allowlist: IterableSet<AccountId>,
+    mint_snapshots: Vector<MintSnapshot>,
+    dust: IterableMap<AccountId, u128>,
+    sponsors: IterableSet<AccountId>,
+    sponsor_contributions: IterableMap<AccountId, HashMap<AccountId, SponsorContribution>>,
+    holder_protection: HolderProtection,
+    withdrawal_delay: u64,
+    reward_withdrawals: IterableMap<AccountId, AnnouncedWithdrawal>,
 }
 
 #[near_bindgen]
@@ -96,56 +203,196 @@ impl Contract {
         rewarder: AccountId,
         token_whitelist: Vec<AccountId>,
     ) -> Self {
-        let mut accounts = TreeMap::new(StorageKey::Accounts);
-        accounts.insert(owner.clone(), 0);
         #[allow(deprecated)]
-        Self {
+        let mut this = Self {
             owner,
             validator,
             rewarder,
             shitzu_token,
             shitzu_nft,
-            accounts,
+            accounts: TreeMap::new(StorageKey::Accounts),
+            holders: TreeMap::new(StorageKey::Holders),
+            holder_index: HolderIndex::Complete,
             deposits: UnorderedMap::new(StorageKey::Deposits),
             rewards: UnorderedMap::new(StorageKey::Rewards),
             shares: 0,
-            token_whitelist: Lazy::new(StorageKey::TokenWhitelist, token_whitelist),
+            tokens: IterableMap::new(StorageKey::Tokens),
+            sponsors: IterableSet::new(StorageKey::Sponsors),
+            sponsor_contributions: IterableMap::new(StorageKey::SponsorContributions),
+            holder_protection: HolderProtection::default(),
+            withdrawal_delay: protection::DEFAULT_WITHDRAWAL_DELAY,
+            reward_withdrawals: IterableMap::new(StorageKey::RewardWithdrawals),
+            pending_payouts: LookupMap::new(StorageKey::PendingPayouts),
+            account_storage_usage: 0,
+            penalty: PenaltyConfig::default(),
+            burnt_rewards: IterableMap::new(StorageKey::BurntRewards),
+            score_multipliers: IterableMap::new(StorageKey::ScoreMultipliers),
+            roles: IterableMap::new(StorageKey::Roles),
+            paused: PauseState::default(),
+            pending_owner: None,
+            staged_upgrade: None,
+            upgrade_delay: upgrade::DEFAULT_UPGRADE_DELAY,
+            transfer_policy: TransferPolicy::default(),
+            transfer_allowlist: IterableSet::new(StorageKey::TransferAllowlist),
+            mint_snapshots: Vector::new(StorageKey::MintSnapshots),
+            dust: IterableMap::new(StorageKey::Dust),
+        };
+        this.score_multipliers
+            .insert(this.shitzu_token.clone(), DEFAULT_SHITZU_SCORE_MULTIPLIER);
+        this.account_storage_usage = this.measure_account_storage_usage();
+        this.accounts.insert(this.owner.clone(), 0);
+        this.accounts.insert(this.validator.clone(), 0);
+        for token_id in token_whitelist {
+            this.tokens.insert(token_id.clone(), TokenInfo::default());
+            Self::fetch_token_metadata(token_id);
         }
+        Self::write_state_version(STATE_VERSION);
+        this
     }
 
+    /// Burns all shares of the caller and redeems them for their part of the wrapped tokens.
     #[payable]
     pub fn burn(&mut self) -> PromiseOrValue<U128> {
+        self.internal_burn(None, None, None)
+    }
+
+    /// Burns `amount` shares of the caller and redeems them for their part of the wrapped tokens.
+    /// The remaining balance stays with the caller as a claim on future rewards.
+    #[payable]
+    pub fn burn_amount(&mut self, amount: U128) -> PromiseOrValue<U128> {
+        require!(amount.0 > 0, "The amount should be a positive number");
+        self.internal_burn(None, Some(amount.0), None)
+    }
+
+    /// Burns shares of the caller like `burn`/`burn_amount`, but sends the redeemed tokens to `receiver_id`.
+    /// The NFT check and the tracked score still belong to the caller.
+    #[payable]
+    pub fn burn_to(
+        &mut self,
+        receiver_id: AccountId,
+        amount: Option<U128>,
+    ) -> PromiseOrValue<U128> {
+        if let Some(amount) = amount {
+            require!(amount.0 > 0, "The amount should be a positive number");
+        }
+        self.internal_burn(Some(receiver_id), amount.map(|amount| amount.0), None)
+    }
+
+    /// Burns shares of the caller, but only redeems the selected `token_ids`.
+    /// The part of all other wrapped tokens that belonged to the burnt shares is forfeited.
+    /// It stays in the contract and increases the value of all remaining shares.
+    #[payable]
+    pub fn burn_tokens(
+        &mut self,
+        token_ids: Vec<AccountId>,
+        amount: Option<U128>,
+        receiver_id: Option<AccountId>,
+    ) -> PromiseOrValue<U128> {
+        require!(!token_ids.is_empty(), "No tokens selected");
+        for (index, token_id) in token_ids.iter().enumerate() {
+            require!(
+                self.rewards.contains_key(token_id),
+                format!("No reward found for token {}", token_id)
+            );
+            require!(
+                !token_ids[..index].contains(token_id),
+                format!("Token {} selected more than once", token_id)
+            );
+        }
+        if let Some(amount) = amount {
+            require!(amount.0 > 0, "The amount should be a positive number");
+        }
+        self.internal_burn(receiver_id, amount.map(|amount| amount.0), Some(token_ids))
+    }
+
+    #[private]
+    pub fn on_burn(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: Option<U128>,
+        token_ids: Option<Vec<AccountId>>,
+        #[callback_unwrap] primary_nft: Option<(TokenId, U128)>,
+    ) -> U128 {
+        let mut balance = self.internal_withdraw_shares(&sender_id, amount.map(|amount| amount.0));
+        let penalty = if primary_nft.is_none() {
+            self.penalty_of(balance)
+        } else {
+            0
+        };
+        balance -= penalty;
+
+        self.internal_payout(
+            &sender_id,
+            &receiver_id,
+            balance,
+            token_ids.as_deref(),
+            primary_nft.as_ref().map(|(primary_nft, _)| primary_nft),
+        );
+        self.shares -= balance;
+
+        FtBurn {
+            owner_id: &sender_id,
+            amount: balance.into(),
+            memo: None,
+        }
+        .emit();
+
+        if penalty > 0 {
+            self.internal_apply_penalty(&sender_id, penalty);
+        }
+        self.internal_collect_dust();
+
+        U128(balance)
+    }
+}
+
+impl Contract {
+    fn internal_burn(
+        &mut self,
+        receiver_id: Option<AccountId>,
+        amount: Option<u128>,
+        token_ids: Option<Vec<AccountId>>,
+    ) -> PromiseOrValue<U128> {
         assert_one_yocto();
+        self.require_not_paused(PausableFeature::Burn);
+        let payouts = token_ids
+            .as_ref()
+            .map(|token_ids| token_ids.len())
+            .unwrap_or_else(|| self.rewards.len() as usize);
         require!(
             env::prepaid_gas()
                 >= GAS_FOR_BURN
                     .saturating_add(GAS_FOR_NFT_CHECK)
                     .saturating_add(
                         GAS_FOR_FT_TRANSFER
-                            .checked_mul(self.rewards.len() as u64)
+                            .saturating_add(GAS_FOR_RESOLVE_PAYOUT)
+                            .checked_mul(payouts as u64)
                             .unwrap()
                     ),
             "Not enough gas attached"
         );
         let sender_id = env::predecessor_account_id();
+        let receiver_id = receiver_id.unwrap_or_else(|| sender_id.clone());
 
-        require!(
-            self.accounts.contains_key(&sender_id),
-            "Account has no tokens"
-        );
+        let balance = self
+            .accounts
+            .get(&sender_id)
+            .copied()
+            .unwrap_or_else(|| env::panic_str("Account has no tokens"));
+        if let Some(amount) = amount {
+            require!(amount <= balance, "Not enough tokens to burn");
+        }
 
         if sender_id == self.owner {
-            let balance = self.accounts.remove(&sender_id).unwrap();
-
-            for (token_id, deposit) in self.rewards.iter_mut() {
-                let amount = (U256::from(balance) * U256::from(*deposit) / U256::from(self.shares))
-                    .as_u128();
-                *deposit -= amount;
-                ext_ft_core::ext(token_id.clone())
-                    .with_unused_gas_weight(1)
-                    .with_attached_deposit(NearToken::from_yoctonear(1))
-                    .ft_transfer(sender_id.clone(), amount.into(), None);
-            }
+            let balance = self.internal_withdraw_shares(&sender_id, amount);
+            self.internal_payout(
+                &sender_id,
+                &receiver_id,
+                balance,
+                token_ids.as_deref(),
+                None,
+            );
             self.shares -= balance;
 
             FtBurn {
@@ -154,83 +401,183 @@ impl Contract {
                 memo: None,
             }
             .emit();
+            self.internal_collect_dust();
 
             PromiseOrValue::Value(U128(balance))
         } else {
             PromiseOrValue::Promise(
                 rewarder::ext(self.rewarder.clone())
                     .with_static_gas(GAS_FOR_NFT_CHECK)
-                    .primary_nft_of(env::predecessor_account_id())
+                    .primary_nft_of(sender_id.clone())
                     .then(
                         Self::ext(env::current_account_id())
                             .with_unused_gas_weight(1)
-                            .on_burn(sender_id),
+                            .on_burn(sender_id, receiver_id, amount.map(U128), token_ids),
                     ),
             )
         }
     }
 
-    #[private]
-    pub fn on_burn(
+    /// Sends `receiver_id` the part of every wrapped token that belongs to `balance` shares.
+    /// If `token_ids` are given, all other tokens are skipped and their part stays in `rewards`.
+    /// If a primary NFT is given, redeemed tokens with a score multiplier are tracked as score for it.
+    /// Failed transfers are credited to the receiver's pending payouts.
+    fn internal_payout(
         &mut self,
-        sender_id: AccountId,
-        #[callback_unwrap] primary_nft: Option<(TokenId, U128)>,
-    ) -> U128 {
-        let mut balance = self.accounts.remove(&sender_id).unwrap();
-
-        if primary_nft.is_none() {
-            let owner_refund = balance / 5;
-            balance -= owner_refund;
-            let owner_balance = self.accounts.get_mut(&self.owner).unwrap();
-            *owner_balance += owner_refund;
-
-            FtTransfer {
-                old_owner_id: &sender_id,
-                new_owner_id: &self.owner,
-                amount: owner_refund.into(),
-                memo: None,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        balance: u128,
+        token_ids: Option<&[AccountId]>,
+        primary_nft: Option<&TokenId>,
+    ) {
+        let mut payouts = vec![];
+        for (token_id, deposit) in self.rewards.iter() {
+            if let Some(token_ids) = token_ids {
+                if !token_ids.contains(token_id) {
+                    continue;
+                }
             }
-            .emit();
+            if !self.token_state(token_id).pays_out() {
+                continue;
+            }
+            payouts.push((token_id.clone(), self.share_of(balance, *deposit)));
         }
+        for (token_id, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            *self.rewards.get_mut(&token_id).unwrap() -= amount;
 
-        for (token_id, deposit) in self.rewards.iter_mut() {
-            let amount =
-                (U256::from(balance) * U256::from(*deposit) / U256::from(self.shares)).as_u128();
-            *deposit -= amount;
-            if token_id == &self.shitzu_token {
-                if let Some((primary_nft, _)) = &primary_nft {
-                    ext_ft_core::ext(token_id.clone())
-                        .with_unused_gas_weight(1)
-                        .with_attached_deposit(NearToken::from_yoctonear(1))
-                        .ft_transfer(sender_id.clone(), amount.into(), None)
-                        .then(
-                            rewarder::ext(self.rewarder.clone())
-                                .with_unused_gas_weight(1)
-                                .on_track_score(primary_nft.clone(), (amount * 3).into()),
-                        );
-                } else {
-                    ext_ft_core::ext(token_id.clone())
+            Event::BurnPayout {
+                account_id: sender_id.clone(),
+                receiver_id: receiver_id.clone(),
+                token_id: token_id.clone(),
+                amount: U128(amount),
+            }
+            .emit();
+            let transfer = Self::payout_transfer(token_id.clone(), receiver_id.clone(), amount);
+            if let (Some(primary_nft), Some(score)) =
+                (primary_nft, self.score_of(&token_id, amount))
+            {
+                transfer.then(
+                    rewarder::ext(self.rewarder.clone())
                         .with_unused_gas_weight(1)
-                        .with_attached_deposit(NearToken::from_yoctonear(1))
-                        .ft_transfer(sender_id.clone(), amount.into(), None);
-                }
-            } else {
-                ext_ft_core::ext(token_id.clone())
-                    .with_unused_gas_weight(1)
-                    .with_attached_deposit(NearToken::from_yoctonear(1))
-                    .ft_transfer(sender_id.clone(), amount.into(), None);
+                        .on_track_score(primary_nft.clone(), score.into()),
+                );
             }
         }
-        self.shares -= balance;
+    }
 
-        FtBurn {
-            owner_id: &sender_id,
-            amount: balance.into(),
-            memo: None,
+    /// Returns the penalty for burning `balance` shares without a primary NFT.
+    fn penalty_of(&self, balance: u128) -> u128 {
+        (U256::from(balance) * U256::from(self.penalty.rate_bps) / U256::from(BPS_DIVISOR))
+            .as_u128()
+    }
+
+    /// Returns the score, that redeeming `amount` of `token_id` adds to the burner's primary NFT.
+    fn score_of(&self, token_id: &AccountId, amount: u128) -> Option<u128> {
+        let multiplier = self.score_multipliers.get(token_id)?;
+        Some((U256::from(amount) * U256::from(*multiplier) / U256::from(BPS_DIVISOR)).as_u128())
+    }
+
+    /// Measures the storage of a registered account, including its entry in the holder index.
+    fn measure_account_storage_usage(&mut self) -> StorageUsage {
+        let initial_storage_usage = env::storage_usage();
+        let tmp_account_id: AccountId = "a".repeat(64).parse().unwrap();
+        self.accounts.insert(tmp_account_id.clone(), 0);
+        self.internal_set_balance(&tmp_account_id, u128::MAX);
+        self.accounts.flush();
+        self.holders.flush();
+        let account_storage_usage = env::storage_usage() - initial_storage_usage;
+        self.internal_set_balance(&tmp_account_id, 0);
+        self.accounts.remove(&tmp_account_id);
+        self.accounts.flush();
+        self.holders.flush();
+        account_storage_usage
+    }
+
+    /// Sets the balance of a registered account and keeps the holder index in sync.
+    pub(crate) fn internal_set_balance(&mut self, account_id: &AccountId, balance: u128) {
+        let old_balance = self
+            .accounts
+            .insert(account_id.clone(), balance)
+            .unwrap_or_else(|| {
+                env::panic_str(&format!("The account {} is not registered", account_id))
+            });
+        if old_balance > 0 {
+            self.holders.remove(&(old_balance, account_id.clone()));
+        }
+        if balance > 0 {
+            self.holders.insert((balance, account_id.clone()), ());
+        }
+    }
+
+    /// Takes `penalty` shares, that have already been removed from `account_id`,
+    /// and moves them according to the configured penalty destination.
+    fn internal_apply_penalty(&mut self, account_id: &AccountId, penalty: u128) {
+        Event::PenaltyApplied {
+            account_id: account_id.clone(),
+            amount: U128(penalty),
+            destination: self.penalty.destination,
         }
         .emit();
+        match self.penalty.destination {
+            PenaltyDestination::Owner => {
+                let owner_id = self.owner.clone();
+                let owner_balance = self.accounts.get(&owner_id).copied().unwrap();
+                self.internal_set_balance(&owner_id, owner_balance + penalty);
+
+                FtTransfer {
+                    old_owner_id: account_id,
+                    new_owner_id: &self.owner,
+                    amount: penalty.into(),
+                    memo: None,
+                }
+                .emit();
+            }
+            PenaltyDestination::Burn => {
+                for (token_id, deposit) in self.rewards.iter_mut() {
+                    let amount = (U256::from(penalty) * U256::from(*deposit)
+                        / U256::from(self.shares))
+                    .as_u128();
+                    *deposit -= amount;
+                    *self.burnt_rewards.entry(token_id.clone()).or_default() += amount;
+                }
+                self.shares -= penalty;
 
-        U128(balance)
+                FtBurn {
+                    owner_id: account_id,
+                    amount: penalty.into(),
+                    memo: Some("penalty"),
+                }
+                .emit();
+            }
+            PenaltyDestination::Redistribute => {
+                self.shares -= penalty;
+
+                FtBurn {
+                    owner_id: account_id,
+                    amount: penalty.into(),
+                    memo: Some("penalty"),
+                }
+                .emit();
+            }
+        }
+    }
+
+    /// Removes `amount` shares (or the whole balance) from an account and returns the removed amount.
+    /// The account stays registered, even if there is no remaining balance.
+    fn internal_withdraw_shares(&mut self, account_id: &AccountId, amount: Option<u128>) -> u128 {
+        let balance = self
+            .accounts
+            .get(account_id)
+            .copied()
+            .unwrap_or_else(|| env::panic_str("Account has no tokens"));
+        let amount = amount.unwrap_or(balance);
+        require!(amount <= balance, "Not enough tokens to burn");
+
+        self.internal_set_balance(account_id, balance - amount);
+        amount
     }
 }
 
@@ -240,30 +587,16 @@ impl FungibleTokenCore for Contract {
     #[payable]
     fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
         assert_one_yocto();
+        self.require_not_paused(PausableFeature::Transfer);
         let sender_id = env::predecessor_account_id();
-        let amount = amount.0;
-        require!(
+        self.require_transfer_allowed(
+            &sender_id,
+            &receiver_id,
             sender_id == self.validator,
-            "Only validator can distribute tokens"
+            "Only validator can distribute tokens",
         );
-        require!(amount > 0, "The amount should be a positive number");
 
-        if !self.accounts.contains_key(&receiver_id) {
-            self.accounts.insert(receiver_id.clone(), 0);
-        }
-        let balance = self.accounts.get_mut(&receiver_id).unwrap();
-        *balance += amount;
-
-        let balance = self.accounts.get_mut(&sender_id).unwrap();
-        *balance -= amount;
-
-        FtTransfer {
-            old_owner_id: &self.validator,
-            new_owner_id: &receiver_id,
-            amount: U128(amount),
-            memo: memo.as_deref(),
-        }
-        .emit();
+        self.internal_transfer(&sender_id, &receiver_id, amount.0, memo.as_deref());
     }
 
     #[payable]
@@ -275,23 +608,21 @@ impl FungibleTokenCore for Contract {
         msg: String,
     ) -> PromiseOrValue<U128> {
         assert_one_yocto();
+        self.require_not_paused(PausableFeature::Transfer);
         require!(
             env::prepaid_gas() > GAS_FOR_FT_TRANSFER_CALL,
             "More gas is required"
         );
         let sender_id = env::predecessor_account_id();
         let amount = amount.0;
-        require!(sender_id == self.owner, "Only owner can call this function");
-        require!(amount > 0, "The amount should be a positive number");
-
-        if !self.accounts.contains_key(&receiver_id) {
-            self.accounts.insert(receiver_id.clone(), 0);
-        }
-        let balance = self.accounts.get_mut(&receiver_id).unwrap();
-        *balance += amount;
+        self.require_transfer_allowed(
+            &sender_id,
+            &receiver_id,
+            sender_id == self.owner,
+            "Only owner can call this function",
+        );
 
-        let balance = self.accounts.get_mut(&sender_id).unwrap();
-        *balance -= amount;
+        self.internal_transfer(&sender_id, &receiver_id, amount, memo.as_deref());
 
         let receiver_gas = env::prepaid_gas()
             .checked_sub(GAS_FOR_FT_TRANSFER_CALL)
@@ -322,28 +653,58 @@ impl FungibleTokenCore for Contract {
 
 #[near_bindgen]
 impl FungibleTokenReceiver for Contract {
-    #[allow(unused_variables)]
     fn ft_on_transfer(
         &mut self,
         sender_id: AccountId,
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128> {
-        require!(sender_id == self.owner, "Only owner can deposit");
-        let token_id = env::predecessor_account_id();
+        self.require_not_paused(PausableFeature::Deposit);
         require!(
-            self.token_whitelist.contains(&token_id),
-            "Token not whitelisted"
+            sender_id == self.owner || self.sponsors.contains(&sender_id),
+            "Only owner or sponsors can deposit"
         );
+        let token_id = env::predecessor_account_id();
+        match self.tokens.get(&token_id).map(|token| token.state) {
+            Some(state) if state.accepts_deposits() => {}
+            Some(TokenState::Sunset) => env::panic_str("Token doesn't accept deposits anymore"),
+            _ => env::panic_str("Token not whitelisted"),
+        }
+
+        let msg = if msg.is_empty() {
+            DepositMsg::Deposit
+        } else {
+            serde_json::from_str(&msg).unwrap_or_else(|_| env::panic_str("Invalid deposit message"))
+        };
+        if let DepositMsg::DepositAndMint { .. } = msg {
+            require!(sender_id == self.owner, "Only owner can mint");
+            require!(
+                self.token_state(&token_id).can_mint(),
+                "Token can't be minted"
+            );
+        }
 
+        Event::TokenDeposited {
+            token_id: token_id.clone(),
+            sender_id: sender_id.clone(),
+            amount,
+        }
+        .emit();
+        if sender_id != self.owner {
+            self.internal_sponsor_deposit(sender_id, token_id.clone(), amount.0);
+        }
         match self.deposits.get_mut(&token_id) {
             Some(deposit) => {
                 *deposit += amount.0;
             }
             None => {
-                self.deposits.insert(token_id, amount.0);
+                self.deposits.insert(token_id.clone(), amount.0);
             }
         }
+
+        if let DepositMsg::DepositAndMint { shares } = msg {
+            self.internal_mint(shares);
+        }
         PromiseOrValue::Value(0.into())
     }
 }
@@ -371,20 +732,35 @@ impl FungibleTokenResolver for Contract {
         };
 
         if unused_amount > 0 {
-            let receiver_balance = self.accounts.get_mut(&receiver_id).unwrap();
-            let refund_amount = std::cmp::min(*receiver_balance, unused_amount);
-            *receiver_balance -= refund_amount;
-
-            let sender_balance = self.accounts.get_mut(&sender_id).unwrap();
-            *sender_balance += refund_amount;
-
-            FtTransfer {
-                old_owner_id: &receiver_id,
-                new_owner_id: &sender_id,
-                amount: U128(refund_amount),
-                memo: Some("refund"),
+            let Some(receiver_balance) = self.accounts.get(&receiver_id).copied() else {
+                // The receiver has been force unregistered and its balance is already burnt.
+                return U128(amount);
+            };
+            let refund_amount = std::cmp::min(receiver_balance, unused_amount);
+            self.internal_set_balance(&receiver_id, receiver_balance - refund_amount);
+
+            if let Some(sender_balance) = self.accounts.get(&sender_id).copied() {
+                self.internal_set_balance(&sender_id, sender_balance + refund_amount);
+
+                FtTransfer {
+                    old_owner_id: &receiver_id,
+                    new_owner_id: &sender_id,
+                    amount: U128(refund_amount),
+                    memo: Some("refund"),
+                }
+                .emit();
+            } else {
+                // The sender has unregistered in the meantime, so the refund is burnt.
+                self.shares -= refund_amount;
+
+                FtBurn {
+                    owner_id: &receiver_id,
+                    amount: U128(refund_amount),
+                    memo: Some("refund"),
+                }
+                .emit();
+                self.internal_collect_dust();
             }
-            .emit();
             let used_amount = amount - refund_amount;
             U128(used_amount)
         } else {
@@ -393,40 +769,116 @@ impl FungibleTokenResolver for Contract {
     }
 }
 
-#[allow(unused_variables)]
 #[near_bindgen]
 impl StorageManagement for Contract {
+    // `registration_only` doesn't matter, because the storage balance is fixed per account.
+    #[allow(unused_variables)]
     #[payable]
     fn storage_deposit(
         &mut self,
         account_id: Option<AccountId>,
         registration_only: Option<bool>,
     ) -> StorageBalance {
-        env::panic_str("unimplemented");
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        if self.accounts.contains_key(&account_id) {
+            log!("The account is already registered, refunding the deposit");
+            if amount > NearToken::from_near(0) {
+                Promise::new(env::predecessor_account_id()).transfer(amount);
+            }
+        } else {
+            let min_balance = self.storage_balance_bounds().min;
+            require!(
+                amount >= min_balance,
+                "The attached deposit is less than the minimum storage balance"
+            );
+
+            self.accounts.insert(account_id.clone(), 0);
+            let refund = amount.saturating_sub(min_balance);
+            if refund > NearToken::from_near(0) {
+                Promise::new(env::predecessor_account_id()).transfer(refund);
+            }
+        }
+        self.storage_balance_of(account_id).unwrap()
     }
 
+    /// The storage balance is fixed per account, so there is never anything available to withdraw.
     #[payable]
     fn storage_withdraw(&mut self, amount: Option<NearToken>) -> StorageBalance {
-        env::panic_str("unimplemented");
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let storage_balance = self
+            .storage_balance_of(account_id.clone())
+            .unwrap_or_else(|| {
+                env::panic_str(&format!("The account {} is not registered", account_id))
+            });
+        if let Some(amount) = amount {
+            require!(
+                amount == NearToken::from_near(0),
+                "The amount is greater than the available storage balance"
+            );
+        }
+        storage_balance
     }
 
+    /// Unregisters the caller and refunds the storage deposit.
+    /// With `force` a remaining balance is burnt without redeeming it.
+    /// The wrapped tokens backing it stay with the remaining holders.
     #[payable]
     fn storage_unregister(&mut self, force: Option<bool>) -> bool {
-        env::panic_str("unimplemented");
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        require!(
+            account_id != self.owner && account_id != self.validator,
+            "Owner and validator can't be unregistered"
+        );
+        let Some(balance) = self.accounts.get(&account_id).copied() else {
+            log!("The account {} is not registered", account_id);
+            return false;
+        };
+        require!(
+            balance == 0 || force.unwrap_or(false),
+            "Can't unregister the account with the positive balance without force"
+        );
+
+        self.internal_set_balance(&account_id, 0);
+        self.accounts.remove(&account_id);
+        if balance > 0 {
+            self.shares -= balance;
+            FtBurn {
+                owner_id: &account_id,
+                amount: balance.into(),
+                memo: Some("unregister"),
+            }
+            .emit();
+            self.internal_collect_dust();
+        }
+        Promise::new(account_id).transfer(
+            self.storage_balance_bounds()
+                .min
+                .saturating_add(NearToken::from_yoctonear(1)),
+        );
+        true
     }
 
     fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        let required_storage_balance =
+            env::storage_byte_cost().saturating_mul(self.account_storage_usage.into());
         StorageBalanceBounds {
-            min: NearToken::from_yoctonear(0),
-            max: None,
+            min: required_storage_balance,
+            max: Some(required_storage_balance),
         }
     }
 
     fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
-        Some(StorageBalance {
-            total: NearToken::from_millinear(50),
-            available: NearToken::from_yoctonear(0),
-        })
+        if self.accounts.contains_key(&account_id) {
+            Some(StorageBalance {
+                total: self.storage_balance_bounds().min,
+                available: NearToken::from_near(0),
+            })
+        } else {
+            None
+        }
     }
 }
 
@@ -440,7 +892,7 @@ impl FungibleTokenMetadataProvider for Contract {
             icon: Some("data:image/webp;base64,UklGRigTAABXRUJQVlA4IBwTAABQQwCdASqgAKAAPlEgjUSjoiEWGUcEOAUEsYBlOy8uf6++T6L/l3vz3h4dQ7ttX0K7cHzRebpp1u9HV5X+a8Q/Jf7Qlu+E+o18n+537P++ek3eb8ZP8T1Avx7+X/5bxP9kHar0AvYn6z/vfCp1KfDPsAfrP43Hgb0AP6J/mPVh/rv/f/pPP7+f/5z/4/6f4CP53/cP+v67XtN9HZUFrbkqg3jiOhIf9d+fq/XJs6r/Z5L7xpBG5mjS90yo+K2LHw7ghrIKlAeZZ8Gfp44l+QRBLRh1Yu9lKSmlJ4nfbQwldKWg6cYZNeYfQR2QyCef9Itfvncpi238a/gl+6egDy+7HtboGfsearFBqJR4LoaYFKzrHkY2PyT/Ymx66R/1GM7cFqgq9jjuLgAUG9wem5YgDviLRB/6hRgn///ftKve+vLVqrLW95xKlu3j4uJ7hEQRpkLz3Rkj0VRGZthrJICzMgC0lHSVjofa/5B8Wyy/DRl84vMsDWd4l3x/TsySGY3g+bR2wqVStDF5bgad7Z30dJ7W3pOMBmbT//mowRXz3glnUh3J7HQchtwsO9JRj3jPXp9sAiWtyMPYXMNoekEKkfDott91IZb7akMScwH9q48l1LZrARRE3CYdIJsHyGPXDliK3B5LrntSTYXeDq47dNooWfJhaJJ5RC9fiO4ZQE3JAzJx9QhR8bCw/iTkKOa6kuNRCGIehNxm5pUoS0i4jBdoAP7tTd/8Uv+RX7rt4nZSVXkrYRAa3T2v4Rd4YxHAoraPwudDnW0uL35+L7nzMXSEfuXArr7ijeBmxqcvmhMS3oJQdT8y2zRMOq+sf281I1H/M7mdmP26yQWKmGtKucDgAUuEoNKFGIN4Vos/thm6HVnnLGrkykjfPSwQpbBoKhFHZelPueaskbR/oaDhlq19T4n6R6qBHCcv+su9auSuk/NfaJiieyF8yxn2Qcp/UPYi+sjkkeJ6PsQiXD6ssPVHf9kEYgzlYzNWZ64wp1rD6dIVkky9fEWhXzafm2I+M/bx+PmBk+12ho3/3NvkxH5Q2f8RROrCyuVnjNRoNMICXauq+hyghReEfqs7kvpF67bhfCAM9BOxsyd078wAmMoJt6JxuL55QYnYzd6kDb4L4m/VENvFBRQeM3E1rAvIaATm/g61kxZtw2G6hFtskXrwdQk2GDYbj87WmhuFxLwnQQmMvumHkM97i+mXw7gx3v5zE651ulNdmP5vk6UIe/NSbf6JKH+x+x+ZMnM7MRFjpWVoJj/QaIyxNziIS2LhwKYtWteparwRPE2OQN4q8hklV5Jp9ndTI4sNooN4QBSHlxMzuHpH8ufaVzO+LRHgkK3jXu/OAqNk8OSkuBXdaI7+d5v15Dnja0yFE8ZQOaVIUmEszl/aotdAZPofKKb+8cLWCD3U391LgV2+kCFj4f6BhKD+Sh6BuHkDyeWKox4wQV7QTY44vHlS/gCFOxGN6i2CDHflhviW9C6cAKNgOJc464lvvbYpL7X5XmmdEj1NFssPNXT1pcjwZiI+tZgxkb4qJsnKLW4D2lmkAzqXfQqoPFBEf+Um5WfMGxTVEihW5SG79sN08amZnd9Z1o00A+4dSgGOVnwsRtirw8lzbqnn+hCCETHoODob8mm4jv9UxWrN5JmPCzy/dd/P8n2ruP9wFwOB4zvNSj9bRlIqLSK21QAm0ku2+lYNA6iwcYpdNEhF2rgy+qLVpujQhFBxHNJHDql47mp4zwOAxnvDnHnL9EAGM6HlfsGWIp3U4Z6/5BoFi60OzNCXynmBcebOu6HmWyhO9OujA3HAdkE2qUFUjELqZPhCMZL+udIth3BaRyQlo1vtC8d4McOIeC7yiM+AABCVwr0bNuIovi1DzA9xEjpBRrLU7EjFiOELBj4I5x0Cakm5kkob9MHaZwp+v3NBts9WzAy8dynZwmfoSidN+10rt4P+jLolkpoUc7K+79W3vVipUu2hc+G8EcTnj0mmejI8MdqANisOsshqDHPDUaI89Vgw5TtNGvRebAL5qIx6XwdMVg0QdSE0wnZ3eObMtdBJjmytWdjIjyW5Pwpty+U8QETcpCvXtk82IDsr+nso9bnDfzhbIiPtn2SlI2EdjyxQzmQYDoz+TmXaAjDQWQiRA5gL6b7z1m9qDbzcyEsU3x/M4kZzJBc6wuYl/WUHiegIHhwgy8P6XwrKYMBPZERBWbQcwQc77JEZfzo+/5MPYurKKtV/NKwWBIkLvchAXEhoi1E883cZMy63LddgfLbt7Ez05twZM037id/Ci3cbS06dPVwnS2+OISXZvTWiAEewuOJsy02XQkCPmcwIKd1cxzK/1yMzwdKSdvJ/jn1pgDikx90w5flfc+TuJ2pddry1rTR4+otsgjEyROr7V2Y87XFelEm81QdfbEszC4kg9w6EKMPHY7l6/kq+8oGJnM7sWdJQYZ9MfDDreDOZOvX5zAuHtSgOllOT2tYZAYAdXNk0i18IbGQX47I971OjWFvS7Qq0HW59zkehiU1aO+41dlF8TJX2RdOaiGGcTgiYb0FupcnJGBgZEFg8zFyrgX53/PBS7svIntLJD6gnWIjKdaOi20anb967C8cMK9fnCWZj/kSBEPK9DKUj4dp6WXXQEm7LuvZWOdBaeQl9pm6p8NZnDKKZn2D0OHOFhhQcgjSGc8jiuuVlj9cleuOqpBXy36pnQ+91KiWEAW2WlH+aNJp0J2pntrrj2nrYogZ073FsBdHyZP3+6flaNyGPXILVo6w6Rzuo5bzT7C6VmChUCpPR0Nhvva1j0BRwlfiHHZ3RQ+j+kKLzl1hf60vQp3oM8iAuvLS1UDnNO+/O9AyYug9G15ocvQ7+7bCPWgz+hgeG1a78A36234sNH0XzNCqCADNXQ9PV6jGG4tkeBA7WK6ZrVVuxHfNgbFJ9Spv3IVhAw0aBTe9M9V0xvUPjc+ON4FgbilL47tILVfc7/PzvakNaKqz21KrGlP8kxkLsrNDkA9c/aG4ry/Ay69nyVbCPbvkCRfmYmbVZyKQwoaOlHZHKeJn0bp2Cle5+qZ8hHs2DfMhC6MPgPwC5oopUx8Vfy1qzqMcNxuGeVhZ8AheeW6Xvcm+2NZTSoclLiOATun666lhkC/yy83rgQS73X0Hwgro+XkpFr1aBQ7KGJPGAkHJKvL5Duc6Z8nygdfXv39Ti8/N5DTcb0VgyrEhrGPX0FJKAd9nhbgiW3KKSsZly+yz5JvgXP5ii3DXRyRy6S/bF/qhKpJd11n4tO5F2Kw55yxuy7jbw6Y2u6Rh+8cttOd9JEYEjYnqZesfqi7HuNPIoyvI7Z8m3bJ/gkCXDHf/JPl9f4ab2CFLw4RFSA0SPMdFtg/XWl1eUnKfNHupWTP2vUvx/1Lpe+ojbj4/VFw3Z7smaxvckdhX6LtltoRuTj0GJlfflMnJVUmBgQNp13KDitdioJkilEflsWAHJSz8yCtUL7+SbKsRHFD8WwyMV7biFgxexzhtYA/+zL2fmxe7Alc1rUX4FCyJtudjkcYeycFkClC9UxTPipPm2VeyQAEePKh1yGDiNRWSil8DbUBMg0n2WUFdmJcHytPXO8osN+ANiVgH8cqTLA6FhP+MtZ0BxreUTjNX55aW4UR0RVn0wEk/ae2JvN7PXCrXdLxl1a/z3iz2aDZtGv7f8maAS/WPfUY1ayP4u81Kydv5RjX2KXyzHXXUm7tcsTZKSUIm6D/CxJWUGnLu9Z+RsyvgjAJu3sDe12aniLzX4X+6qGp9qU+KFh516XU9q+bhdoZcuaW28sreZeDyW3n7errEvf4WNOQZzf5ouFJjr7Uqe5/ZQVtskXRLoeaaT4F9bYQZEX3aei1vhzcXm7Fm3ZZ6q3PkZfNkVbdOHjnY9hPA4gzSl4wC6G6dE8rd2XWrGocjnHFsHfaLTt84vc4oRyGEY/wXtHh/LQkvg1Nfjy0Ct2g+MGKlvN0MRiQkk5hHg3OAzUhfNVvSuzaz35Y0LnQvp3ygZ+uWWt7nbQJfqEFGaWCR19vZHKLrt3CKNgx0jBvHzzFY/oi1H+ehynF9Iwc2NcVQ0H25xUVcpuzpxb47FBFqamHncQ+OoKIr6BJIWUdPOwu9LGZthcjeSWbfdOg2FPvkylvkFNHOK1msxZePkV5DfbafjP4mB4cFEz3WKiD+mK4kIgo427IrMz0kzEhZuWgGMCQ9s54JXjhG4NnsghGawSzM8MZcLhItOF0IzMCDbiC9qLKwhtYoY1wlN0q3hDH4sTmBSXXl8NgBpRI2xlOGY99KRFNa+zhNjCDXqDfU0EeqUgL0zYLiCc1pUQrq5NHytjQnRnUmZG5N/RGT9fUrp51gNN+fn7VicW2uKbSkN3Eb8ljJpDIHGScBVjo6k5PnDM5SzM2go1357RZjlmu/AUez1wNWENC6QfpaYqTeWUaKOnv1lNhEJcWxAmD/O04D7YQBeSOTDSk91GclVDaBLHNj9we6QG2NaGDqjhvIVkpd3SVOW4/n4GqahcgW4hnJboflInhDgsngaoMGKL74Dj15yLJgvwGt5WLOvbG1plma7UW+z7oNURDUwSNxjfWvQ9Vmt6Km0/emQ17VLDvA1KEGlykScagQs8XKRJxqBuciObJAN0xdu4DU6zDshG7luENwvgq3YzS2bNzpFy+hXo6KZSARvd+OyYc+aX1akCkvqeXi4A5Z2jsWuKA90kHhPNUoo8gF1mkla/33hR54kPRxn+/z0TJ8KH1w4pazXBityXcSIamGFtNy5wdfUJi5ZyHWJcg5MAvQbU92RlK2h+0wW2l/c+BFLOI+c9HKsYcHf4AUF26h2nVPJr1kF8OHxTEvC4V64uAPtQbwOUkRcRBMpQUWgMRychgqFpJGHGOkp62RqSn4Fchkgpo6u1yPR0Kwax3O2I7MBp1WTmD1pFa1uNOXf8iDCXCrKJFP7LAoOISgyhanEJ6d6lb/dM/IluOKeajU/kt4w+gGRX+poTkfv/NJyL3a4LiBcpcv2L6acGg9z8u5OOhASeIR7cORTzUzzFtCXsiSkcTyAoOdaSLuGEPkkuWZteyytDb+Tm/lYLjBKBa2LHcgEuhQiNzi+6yhtAr4tkyXZ4ETgoLchPzNtrCUp+MExR8ijPLVpZQJRmhPzPOYBew1vlznBJjPLXvFLiBRqZ5ngcvGqbfv641tN16ijvFQoUUPknPV++dUMDTJzEkeTeALgJg/rPUqYhbEOAWWAYwqrbYCRqJnq/PhC4YPwv14Df4CjF7LMLHjXy7Hp9hCzGlAFvWg6l8+Ij7FfvN2T9+EHSp9O5aIdXx3ZA3Hv0YFOj9NUXgCIUElfdAcCk22Ywg5i3qWCtLi0ZI7WFEdxJlL0ODhONrE6ARx4LQTM81Z8Dll8i5LNdnO1nIPti0Klo3H5/1+mXSwNwSZ28DTjdjTCh1YZ7gyj4xcqD4p4+lTbTYxB3nmSnL9htv3AqQ+DJ7ldqeXwJRs7xNW/HP9bwu76P+64/1uJgskMNELMwBoQFtQUS0IS8KvSkU4bLlHofrj8lZa38Pn4lEuP5eWTDl067UOQTarR7yzD/PjgC08UQMCMPZN9RRkI4N+ZjRGg7rDZeQHUjBmRmDEuo4zs/s7i9Ajinkts/ckyihTHC15aqOEVNVhn9rY/5hRlFv+DHi1n6h7rqjwRu27FtrMIPFFUhUoNrzuSiVmAPb1Ho/K7+LeC9pOETNuHmPNZBKpsoPyKnxlLu6hrm8LkSQBcdqkdBxQhe+EhJqpGLduGlqABegeiS7sxbbhFTIUNyaYQc9adlbXmNocDpgwVQdvp+1BY2JR7sCIc5tiFXciHN+BcQ8HGPigIwRX6LT9YDd36kr+rxSsVdKGbp0bBUF3JF7U/fdeXmKPmAFJrcSVrIKh9ujsmdYmNO7s8D24dB6nhyaAG5pZTOBI7vW3zWobfAVzcnzFa5ZqFM5CpYMnmGmb7Iv61NBg4KNAdf3/Gin6zcyRpAnwyXassq4nxOx1ZdM4yFzhuioQgXG1QXXwDdZfAfHm3Ul90SxSzi0Ic+PFuC4JbsCR+6q8PTqs0xte8FvHK5d6iIbQcBEidz//0gP3WFFYysy2xmnmI6mDCHbBcb2y3FX1cK8CV5magAPw8t3o66WOogn26lUebpYF1d83N+czSr9P5rhoCHCUpi4PmAdIjFLwTiotePAK0OaVAqfO7oSUqoMbjIH79G3sTZPIwn3mH55+4rCDtvJy1NatAKYh3cBdqs4VK6rpZaULNKmMA8GNq7KEuOJpncBEHTzO0Ij14NF9FshnHnB4b+SJ2Ml9tTgD9mEJBkRQW4yWjXl16m3CpOQ2MNhXLfvDDX5muRcQUaS6P3ElLJ/xtnoIAQQ9pLIeacoWGx46frK4vE0CV4XILFZFADcQIyq7EjjmRm0BE9cYXn5VXZ4qLfKh2vhMt4PADHLRiUrNKNDwdSa7i01Lw+t9fGffpBMfrhbnGHVOCddnijho3021X1SlrfC015jBj7gtCtDRPAGjIsGc5FOf8M9P+oLkAAA==".to_string()),
             reference: None,
             reference_hash: None,
-            decimals: 24,
+            decimals: SHARE_DECIMALS,
         }
     }
 }
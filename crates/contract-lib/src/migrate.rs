@@ -0,0 +1,191 @@
+use crate::{
+    protection::DEFAULT_WITHDRAWAL_DELAY, upgrade::DEFAULT_UPGRADE_DELAY, Contract, ContractExt,
+    HolderProtection, PauseState, PenaltyConfig, StorageKey, TokenInfo, TokenState, TransferPolicy,
+    DEFAULT_SHITZU_SCORE_MULTIPLIER,
+};
+#[allow(deprecated)]
+use near_sdk::store::UnorderedMap;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env, near, near_bindgen, require,
+    store::{IterableMap, IterableSet, Lazy, LookupMap, TreeMap, Vector},
+    AccountId,
+};
+use std::ops::Bound;
+
+/// Version of the state layout of `Contract`.
+/// Bump it with every layout change and add a migration from the previous layout to `migrate`.
+pub const STATE_VERSION: u32 = 2;
+
+/// Progress of the holder index, that is built in batches via `index_holders` after a migration.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HolderIndex {
+    /// All accounts up to `last_account` (ordered by account ID) have been indexed.
+    Building {
+        last_account: Option<AccountId>,
+    },
+    Complete,
+}
+
+/// State layout before it was versioned.
+/// Contracts without a stored state version are on this layout.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "near_sdk::borsh")]
+#[allow(deprecated)]
+pub struct ContractV1 {
+    owner: AccountId,
+    validator: AccountId,
+    rewarder: AccountId,
+    shitzu_token: AccountId,
+    shitzu_nft: AccountId,
+    accounts: TreeMap<AccountId, u128>,
+    deposits: UnorderedMap<AccountId, u128>,
+    rewards: UnorderedMap<AccountId, u128>,
+    shares: u128,
+    token_whitelist: Lazy<Vec<AccountId>>,
+}
+
+impl From<ContractV1> for Contract {
+    fn from(old: ContractV1) -> Self {
+        let mut tokens = IterableMap::new(StorageKey::Tokens);
+        for token_id in old.token_whitelist.iter() {
+            tokens.insert(token_id.clone(), TokenInfo::default());
+        }
+        // tokens that have been removed from the whitelist, but still have deposits or rewards
+        for token_id in old.deposits.keys().chain(old.rewards.keys()) {
+            if !tokens.contains_key(token_id) {
+                tokens.insert(
+                    token_id.clone(),
+                    TokenInfo {
+                        state: TokenState::Sunset,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+        env::storage_remove(&borsh::to_vec(&StorageKey::TokenWhitelist).unwrap());
+
+        let mut this = Self {
+            owner: old.owner,
+            validator: old.validator,
+            rewarder: old.rewarder,
+            shitzu_token: old.shitzu_token,
+            shitzu_nft: old.shitzu_nft,
+            accounts: old.accounts,
+            holders: TreeMap::new(StorageKey::Holders),
+            // existing accounts are indexed via `index_holders`, so that the migration doesn't depend on their number
+            holder_index: HolderIndex::Building { last_account: None },
+            deposits: old.deposits,
+            rewards: old.rewards,
+            shares: old.shares,
+            tokens,
+            pending_payouts: LookupMap::new(StorageKey::PendingPayouts),
+            account_storage_usage: 0,
+            penalty: PenaltyConfig::default(),
+            burnt_rewards: IterableMap::new(StorageKey::BurntRewards),
+            score_multipliers: IterableMap::new(StorageKey::ScoreMultipliers),
+            roles: IterableMap::new(StorageKey::Roles),
+            paused: PauseState::default(),
+            pending_owner: None,
+            staged_upgrade: None,
+            upgrade_delay: DEFAULT_UPGRADE_DELAY,
+            transfer_policy: TransferPolicy::default(),
+            transfer_allowlist: IterableSet::new(StorageKey::TransferAllowlist),
+            mint_snapshots: Vector::new(StorageKey::MintSnapshots),
+            dust: IterableMap::new(StorageKey::Dust),
+            sponsors: IterableSet::new(StorageKey::Sponsors),
+            sponsor_contributions: IterableMap::new(StorageKey::SponsorContributions),
+            holder_protection: HolderProtection::default(),
+            withdrawal_delay: DEFAULT_WITHDRAWAL_DELAY,
+            reward_withdrawals: IterableMap::new(StorageKey::RewardWithdrawals),
+        };
+        this.score_multipliers
+            .insert(this.shitzu_token.clone(), DEFAULT_SHITZU_SCORE_MULTIPLIER);
+        this.account_storage_usage = this.measure_account_storage_usage();
+        if !this.accounts.contains_key(&this.validator) {
+            this.accounts.insert(this.validator.clone(), 0);
+        }
+        this
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Migrates the state from the stored state version to the current layout.
+    /// It is called without arguments by `upgrade_and_migrate` right after the new code is deployed.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let this = match Self::state_version() {
+            1 => Self::from(Self::read_state::<ContractV1>()),
+            STATE_VERSION => env::panic_str("State is already up to date"),
+            version => env::panic_str(&format!("No migration from state version {}", version)),
+        };
+        Self::write_state_version(STATE_VERSION);
+        this
+    }
+
+    /// Adds the next `limit` accounts to the holder index after a migration.
+    /// Balance changes keep already indexed accounts up to date, so it can be called in as many transactions as needed.
+    /// Returns `true` once all accounts have been indexed.
+    pub fn index_holders(&mut self, limit: u32) -> bool {
+        self.require_owner();
+        require!(limit > 0, "limit must be positive");
+        let HolderIndex::Building { last_account } = &self.holder_index else {
+            env::panic_str("Holder index is already complete");
+        };
+        let start = last_account
+            .as_ref()
+            .map_or(Bound::Unbounded, Bound::Excluded);
+        let accounts: Vec<_> = self
+            .accounts
+            .range::<_, AccountId>((start, Bound::Unbounded))
+            .take(limit as usize)
+            .map(|(account_id, balance)| (account_id.clone(), *balance))
+            .collect();
+        for (account_id, balance) in &accounts {
+            if *balance > 0 {
+                self.holders.insert((*balance, account_id.clone()), ());
+            }
+        }
+
+        self.holder_index = match accounts.last() {
+            Some((account_id, _)) if accounts.len() == limit as usize => HolderIndex::Building {
+                last_account: Some(account_id.clone()),
+            },
+            _ => HolderIndex::Complete,
+        };
+        self.holder_index == HolderIndex::Complete
+    }
+}
+
+impl Contract {
+    pub(crate) fn require_holder_index(&self) {
+        require!(
+            self.holder_index == HolderIndex::Complete,
+            "Holder index is still being built"
+        );
+    }
+
+    /// Reads the state version, that is stored next to the contract state.
+    pub(crate) fn state_version() -> u32 {
+        env::storage_read(&borsh::to_vec(&StorageKey::StateVersion).unwrap())
+            .map(|bytes| {
+                u32::try_from_slice(&bytes)
+                    .unwrap_or_else(|_| env::panic_str("Invalid state version"))
+            })
+            .unwrap_or(1)
+    }
+
+    pub(crate) fn write_state_version(version: u32) {
+        env::storage_write(
+            &borsh::to_vec(&StorageKey::StateVersion).unwrap(),
+            &borsh::to_vec(&version).unwrap(),
+        );
+    }
+
+    fn read_state<T: BorshDeserialize>() -> T {
+        env::state_read().unwrap_or_else(|| env::panic_str("Contract state not found"))
+    }
+}
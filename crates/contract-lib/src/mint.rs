@@ -0,0 +1,105 @@
+use crate::{Contract, ContractExt, Event, ONE_SHARE};
+use near_sdk::{json_types::U128, near, near_bindgen, require, AccountId};
+use primitive_types::U256;
+
+/// How `mint_with_mode` calculates the number of shares from the pending deposits.
+#[near(serializers = [json])]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[derive(Clone, Debug)]
+pub enum MintMode {
+    /// Keeps the current amount of `reference_token` per share.
+    PreserveValue { reference_token: AccountId },
+    /// Prices one share (10^24) at `price` of `reference_token`.
+    TargetPrice {
+        reference_token: AccountId,
+        price: U128,
+    },
+}
+
+/// Result of `mint_with_mode`.
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub struct MintResult {
+    pub shares: U128,
+    /// Wrapped token amounts per share (10^24) after the mint.
+    pub share_values: Vec<(AccountId, U128)>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Mints the number of shares calculated by `mode` for the pending deposits.
+    pub fn mint_with_mode(&mut self, mode: MintMode) -> MintResult {
+        self.require_owner();
+        let shares = U128(self.shares_for_mode(&mode));
+        self.internal_mint(shares);
+
+        let share_values = self.share_values();
+        Event::MintPriced {
+            shares,
+            share_values: share_values.clone(),
+        }
+        .emit();
+        MintResult {
+            shares,
+            share_values,
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn shares_for_mode(&self, mode: &MintMode) -> u128 {
+        let (MintMode::PreserveValue { reference_token }
+        | MintMode::TargetPrice {
+            reference_token, ..
+        }) = mode;
+        let deposit = self
+            .deposits
+            .get(reference_token)
+            .copied()
+            .filter(|_| self.token_state(reference_token).can_mint())
+            .unwrap_or_default();
+        require!(deposit > 0, "No mintable deposit found for reference token");
+
+        let shares = match mode {
+            MintMode::PreserveValue { reference_token } => {
+                let reward = self
+                    .rewards
+                    .get(reference_token)
+                    .copied()
+                    .unwrap_or_default();
+                require!(
+                    self.shares > 0 && reward > 0,
+                    "Reference token doesn't back any shares yet"
+                );
+                U256::from(self.shares) * U256::from(deposit) / U256::from(reward)
+            }
+            MintMode::TargetPrice { price, .. } => {
+                require!(price.0 > 0, "The price should be a positive number");
+                U256::from(deposit) * U256::from(ONE_SHARE) / U256::from(price.0)
+            }
+        };
+        require!(
+            shares <= U256::from(u128::MAX),
+            "Too many shares for the deposit"
+        );
+        let shares = shares.as_u128();
+        require!(shares > 0, "Deposit is too small to mint any shares");
+        shares
+    }
+
+    /// Returns the wrapped token amounts per share (10^24).
+    pub(crate) fn share_values(&self) -> Vec<(AccountId, U128)> {
+        if self.shares == 0 {
+            return vec![];
+        }
+        self.rewards
+            .iter()
+            .map(|(token_id, reward)| (token_id.clone(), U128(share_value(*reward, self.shares))))
+            .collect()
+    }
+}
+
+/// Returns the amount per share (10^24), if `amount` backs `supply` shares.
+pub(crate) fn share_value(amount: u128, supply: u128) -> u128 {
+    (U256::from(amount) * U256::from(ONE_SHARE) / U256::from(supply)).as_u128()
+}
@@ -1,148 +1,299 @@
-use crate::{Contract, ContractExt};
-use near_contract_standards::fungible_token::{
-    core::ext_ft_core,
-    events::{FtBurn, FtMint},
+use crate::{
+    Contract, ContractExt, Event, PayoutSource, PenaltyConfig, PenaltyDestination, Role,
+    BPS_DIVISOR,
 };
-use near_sdk::{env, json_types::U128, near_bindgen, require, AccountId, NearToken, Promise};
+use near_contract_standards::fungible_token::events::FtMint;
+use near_sdk::{env, json_types::U128, near_bindgen, require, AccountId, Promise};
 
 #[near_bindgen]
 impl Contract {
-    pub fn whitelist_add_token(&mut self, token_id: AccountId) {
+    /// Proposes `new_owner_id` as the next owner. The handover is only done,
+    /// once the proposed account calls `accept_owner`.
+    /// Proposing another account replaces the pending proposal.
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
         self.require_owner();
-        self.token_whitelist.push(token_id);
+        require!(new_owner_id != self.owner, "Account is already the owner");
+        self.pending_owner = Some(new_owner_id.clone());
+
+        Event::OwnerProposed { new_owner_id }.emit();
     }
 
-    pub fn whitelist_remove_token(&mut self, token_id: AccountId) {
-        self.require_owner();
-        if let Some(index) = self
-            .token_whitelist
-            .iter()
-            .enumerate()
-            .find_map(|(index, token)| {
-                if token == &token_id {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
-        {
-            (*self.token_whitelist).remove(index);
-        } else {
-            env::panic_str("Token not found in whitelist")
+    pub fn accept_owner(&mut self) {
+        let new_owner_id = env::predecessor_account_id();
+        require!(
+            self.pending_owner.as_ref() == Some(&new_owner_id),
+            "Only the proposed owner can accept ownership"
+        );
+        self.pending_owner = None;
+        if !self.accounts.contains_key(&new_owner_id) {
+            self.accounts.insert(new_owner_id.clone(), 0);
         }
+        let old_owner_id = std::mem::replace(&mut self.owner, new_owner_id.clone());
+
+        Event::OwnerTransferred {
+            old_owner_id,
+            new_owner_id,
+        }
+        .emit();
     }
 
-    pub fn withdraw(&mut self, token_id: AccountId, amount: U128) -> Promise {
+    /// Sets the staking pool, that distributes shares via `ft_transfer`.
+    /// Shares still held by the previous pool are moved to the owner,
+    /// so that they can be distributed via the new pool.
+    pub fn set_validator(&mut self, validator_id: AccountId) {
         self.require_owner();
-        let deposit = self.deposits.get_mut(&token_id).unwrap();
-        *deposit -= amount.0;
-        ext_ft_core::ext(token_id)
-            .with_unused_gas_weight(1)
-            .with_attached_deposit(NearToken::from_yoctonear(1))
-            .ft_transfer(self.owner.clone(), amount, None)
+        require!(validator_id != self.validator, "Validator is already set");
+        if !self.accounts.contains_key(&validator_id) {
+            self.accounts.insert(validator_id.clone(), 0);
+        }
+        let old_validator_id = std::mem::replace(&mut self.validator, validator_id.clone());
+        let balance = self
+            .accounts
+            .get(&old_validator_id)
+            .copied()
+            .unwrap_or_default();
+        if balance > 0 && old_validator_id != self.owner {
+            let owner_id = self.owner.clone();
+            self.internal_transfer(
+                &old_validator_id,
+                &owner_id,
+                balance,
+                Some("Validator change"),
+            );
+        }
+
+        Event::ValidatorUpdated {
+            old_validator_id,
+            new_validator_id: validator_id,
+        }
+        .emit();
     }
 
-    pub fn withdraw_reward(&mut self, token_id: AccountId, amount: U128) -> Promise {
+    pub fn set_rewarder(&mut self, rewarder_id: AccountId) {
         self.require_owner();
-        if amount.0 == 0 {
-            env::panic_str("amount must be positive");
+        let old_rewarder_id = std::mem::replace(&mut self.rewarder, rewarder_id.clone());
+
+        Event::RewarderUpdated {
+            old_rewarder_id,
+            new_rewarder_id: rewarder_id,
         }
-        let reward = self.rewards.get_mut(&token_id).unwrap();
-        *reward -= amount.0;
-        if *reward == 0 {
-            self.rewards.remove(&token_id);
+        .emit();
+    }
+
+    pub fn set_shitzu_nft(&mut self, nft_id: AccountId) {
+        self.require_owner();
+        let old_nft_id = std::mem::replace(&mut self.shitzu_nft, nft_id.clone());
+
+        Event::ShitzuNftUpdated {
+            old_nft_id,
+            new_nft_id: nft_id,
         }
-        ext_ft_core::ext(token_id)
-            .with_unused_gas_weight(1)
-            .with_attached_deposit(NearToken::from_yoctonear(1))
-            .ft_transfer(self.owner.clone(), amount, None)
+        .emit();
     }
 
-    pub fn remove_reward(&mut self, token_id: AccountId) -> Promise {
+    /// Sets the penalty in basis points for burners without a primary NFT and where it goes.
+    pub fn set_penalty(&mut self, rate_bps: u16, destination: PenaltyDestination) {
         self.require_owner();
-        let amount = self.rewards.remove(&token_id).unwrap();
-        if amount == 0 {
-            env::panic_str("No reward found for token");
-        }
-        ext_ft_core::ext(token_id)
-            .with_unused_gas_weight(1)
-            .with_attached_deposit(NearToken::from_yoctonear(1))
-            .ft_transfer(self.owner.clone(), U128(amount), None)
+        require!(rate_bps <= BPS_DIVISOR, "Penalty rate can't exceed 100%");
+        self.penalty = PenaltyConfig {
+            rate_bps,
+            destination,
+        };
+
+        Event::PenaltyUpdated {
+            rate_bps,
+            destination,
+        }
+        .emit();
     }
 
-    pub fn mint(&mut self, shares: U128) {
+    /// Sets how redeemed `token_id` counts towards the score of the burner's primary NFT.
+    /// The multiplier is given in basis points. `None` stops tracking score for the token.
+    pub fn set_score_multiplier(&mut self, token_id: AccountId, multiplier_bps: Option<u32>) {
         self.require_owner();
-        require!(!self.deposits.is_empty(), "No tokens have been deposited");
-        for (token_id, amount) in self.deposits.drain() {
-            if let Some(reward) = self.rewards.get_mut(&token_id) {
-                *reward += amount;
-            } else {
-                self.rewards.insert(token_id, amount);
+        match multiplier_bps {
+            Some(multiplier_bps) => {
+                self.score_multipliers
+                    .insert(token_id.clone(), multiplier_bps);
+            }
+            None => {
+                self.score_multipliers.remove(&token_id);
             }
         }
 
-        if shares.0 > 0 {
-            self.shares += shares.0;
-            let owner_shares = self.accounts.get_mut(&self.owner).unwrap();
-            *owner_shares += shares.0;
-            FtMint {
-                owner_id: &self.owner,
-                amount: shares,
-                memo: None,
-            }
-            .emit();
+        Event::ScoreMultiplierUpdated {
+            token_id,
+            multiplier_bps,
         }
+        .emit();
     }
 
-    // A bug in old `mint` function caused owner shares to have a wrong value.
-    // Tx ID: 9tuXaBjngENxiyE2NUF4FdyvJQbXcVEEyEiiNVdShFKv
-    #[private]
-    pub fn migrate(&mut self) {
-        let owner_shares = self.accounts.get_mut(&self.owner).unwrap();
-
-        FtBurn {
-            owner_id: &self.owner,
-            amount: (*owner_shares).into(),
-            memo: None,
+    /// Sends pending deposits to `receiver_id` (the owner by default).
+    /// Only the owner can send them to another account.
+    /// The deposit is restored, if the transfer fails.
+    pub fn withdraw(
+        &mut self,
+        token_id: AccountId,
+        amount: U128,
+        receiver_id: Option<AccountId>,
+    ) -> Promise {
+        self.require_role(Role::Operator);
+        let receiver_id = receiver_id.unwrap_or_else(|| self.owner.clone());
+        require!(
+            receiver_id == self.owner || env::predecessor_account_id() == self.owner,
+            "Only owner can withdraw to another account"
+        );
+        if amount.0 == 0 {
+            env::panic_str("amount must be positive");
+        }
+        let sponsored = self.sponsored_deposit(&token_id);
+        let deposit = self
+            .deposits
+            .get_mut(&token_id)
+            .unwrap_or_else(|| env::panic_str("No deposit found for token"));
+        require!(*deposit >= amount.0, "Not enough deposit");
+        require!(
+            *deposit - sponsored >= amount.0,
+            "Sponsor deposits can only be refunded to the sponsor"
+        );
+        *deposit -= amount.0;
+        if *deposit == 0 {
+            self.deposits.remove(&token_id);
         }
-        .emit();
 
-        *owner_shares -= 185110043485353047406481875436;
-        FtMint {
-            owner_id: &self.owner,
-            amount: (*owner_shares).into(),
-            memo: None,
+        Event::DepositWithdrawn {
+            token_id: token_id.clone(),
+            receiver_id: receiver_id.clone(),
+            amount,
         }
         .emit();
+        Self::owner_payout_transfer(PayoutSource::Deposit, token_id, receiver_id, amount.0, None)
     }
 
-    pub fn upgrade(&self) -> Promise {
+    /// Sends rewards to `receiver_id` (the owner by default).
+    /// The rewards are restored, if the transfer fails.
+    pub fn withdraw_reward(
+        &mut self,
+        token_id: AccountId,
+        amount: U128,
+        receiver_id: Option<AccountId>,
+    ) -> Promise {
         self.require_owner();
+        if amount.0 == 0 {
+            env::panic_str("amount must be positive");
+        }
+        let reward = self
+            .rewards
+            .get(&token_id)
+            .copied()
+            .unwrap_or_else(|| env::panic_str("No reward found for token"));
+        require!(reward >= amount.0, "Not enough reward");
+        let protected = self.internal_protect_reward_withdrawal(&token_id, amount.0);
+        if reward == amount.0 {
+            self.rewards.remove(&token_id);
+        } else {
+            self.rewards.insert(token_id.clone(), reward - amount.0);
+        }
 
-        let code = env::input().expect("Error: No input").to_vec();
-
-        Promise::new(env::current_account_id())
-            .deploy_contract(code)
-            .as_return()
+        let receiver_id = receiver_id.unwrap_or_else(|| self.owner.clone());
+        Event::RewardWithdrawn {
+            token_id: token_id.clone(),
+            receiver_id: receiver_id.clone(),
+            amount,
+        }
+        .emit();
+        Self::owner_payout_transfer(
+            PayoutSource::Reward,
+            token_id,
+            receiver_id,
+            amount.0,
+            protected,
+        )
     }
 
-    pub fn upgrade_and_migrate(&self) -> Promise {
+    /// Sends all rewards of `token_id` to `receiver_id` (the owner by default).
+    /// The rewards are restored, if the transfer fails.
+    pub fn remove_reward(
+        &mut self,
+        token_id: AccountId,
+        receiver_id: Option<AccountId>,
+    ) -> Promise {
         self.require_owner();
+        let amount = self
+            .rewards
+            .get(&token_id)
+            .copied()
+            .filter(|amount| *amount > 0)
+            .unwrap_or_else(|| env::panic_str("No reward found for token"));
+        let protected = self.internal_protect_reward_withdrawal(&token_id, amount);
+        self.rewards.remove(&token_id);
 
-        let code = env::input().expect("Error: No input").to_vec();
+        let receiver_id = receiver_id.unwrap_or_else(|| self.owner.clone());
+        Event::RewardRemoved {
+            token_id: token_id.clone(),
+            receiver_id: receiver_id.clone(),
+            amount: U128(amount),
+        }
+        .emit();
+        Self::owner_payout_transfer(
+            PayoutSource::Reward,
+            token_id,
+            receiver_id,
+            amount,
+            protected,
+        )
+    }
 
-        Promise::new(env::current_account_id())
-            .deploy_contract(code)
-            .then(Self::ext(env::current_account_id()).migrate())
-            .as_return()
+    pub fn mint(&mut self, shares: U128) {
+        self.require_owner();
+        self.internal_mint(shares);
     }
 }
 
 impl Contract {
-    fn require_owner(&self) {
+    /// Adds all mintable deposits to the rewards and mints `shares` to the owner.
+    pub(crate) fn internal_mint(&mut self, shares: U128) {
+        require!(!self.deposits.is_empty(), "No tokens have been deposited");
+        // deposits of `DepositOnly` tokens stay pending until the token is activated
+        let deposits: Vec<(AccountId, u128)> = self
+            .deposits
+            .iter()
+            .filter(|(token_id, _)| self.token_state(token_id).can_mint())
+            .map(|(token_id, amount)| (token_id.clone(), *amount))
+            .collect();
         require!(
-            env::predecessor_account_id() == self.owner,
-            "Only owner can call this function"
+            !deposits.is_empty(),
+            "No mintable tokens have been deposited"
         );
+        self.require_value_preserving_mint(&deposits, shares.0);
+        for (token_id, amount) in deposits {
+            self.deposits.remove(&token_id);
+            self.internal_mint_sponsor_deposits(&token_id);
+            Event::RewardAdded {
+                token_id: token_id.clone(),
+                amount: U128(amount),
+            }
+            .emit();
+            if let Some(reward) = self.rewards.get_mut(&token_id) {
+                *reward += amount;
+            } else {
+                self.rewards.insert(token_id, amount);
+            }
+        }
+
+        if shares.0 > 0 {
+            self.shares += shares.0;
+            let owner_id = self.owner.clone();
+            let owner_shares = self.accounts.get(&owner_id).copied().unwrap();
+            self.internal_set_balance(&owner_id, owner_shares + shares.0);
+            FtMint {
+                owner_id: &self.owner,
+                amount: shares,
+                memo: None,
+            }
+            .emit();
+        }
+
+        self.record_mint_snapshot(shares.0);
     }
 }
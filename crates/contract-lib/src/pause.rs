@@ -0,0 +1,81 @@
+use crate::{Contract, ContractExt, Event, HolderProtection, Role};
+use near_sdk::{env, near, near_bindgen, require};
+
+/// Features of the contract that can be paused independently.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PausableFeature {
+    /// All variants of `burn`.
+    Burn,
+    /// `ft_transfer` and `ft_transfer_call` of shares.
+    Transfer,
+    /// Deposits of wrapped tokens via `ft_on_transfer`.
+    Deposit,
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, Default)]
+pub struct PauseState {
+    pub burn: bool,
+    pub transfer: bool,
+    pub deposit: bool,
+}
+
+impl PauseState {
+    fn get_mut(&mut self, feature: PausableFeature) -> &mut bool {
+        match feature {
+            PausableFeature::Burn => &mut self.burn,
+            PausableFeature::Transfer => &mut self.transfer,
+            PausableFeature::Deposit => &mut self.deposit,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Burns can't be paused while an upgrade, a reward withdrawal or disabling the holder protection is pending,
+    /// because their delay is meant to let holders burn their shares first.
+    pub fn pause(&mut self, feature: PausableFeature) {
+        self.require_role(Role::Pauser);
+        if feature == PausableFeature::Burn {
+            require!(
+                !self.has_pending_timelock(),
+                "Can't pause burns while a timelocked change is pending"
+            );
+        }
+        let paused = self.paused.get_mut(feature);
+        require!(!*paused, "Already paused");
+        *paused = true;
+
+        Event::Paused { feature }.emit();
+    }
+
+    pub fn unpause(&mut self, feature: PausableFeature) {
+        self.require_role(Role::Pauser);
+        let paused = self.paused.get_mut(feature);
+        require!(*paused, "Not paused");
+        *paused = false;
+
+        Event::Unpaused { feature }.emit();
+    }
+}
+
+impl Contract {
+    /// Whether a change is pending, that holders should be able to exit before.
+    fn has_pending_timelock(&self) -> bool {
+        let disabling_protection = matches!(
+            self.holder_protection,
+            HolderProtection::Disabling { disabled_at } if env::block_timestamp() < disabled_at.0
+        );
+        self.staged_upgrade.is_some() || !self.reward_withdrawals.is_empty() || disabling_protection
+    }
+
+    pub(crate) fn require_not_paused(&self, feature: PausableFeature) {
+        let (paused, msg) = match feature {
+            PausableFeature::Burn => (self.paused.burn, "Burning is paused"),
+            PausableFeature::Transfer => (self.paused.transfer, "Transfers are paused"),
+            PausableFeature::Deposit => (self.paused.deposit, "Deposits are paused"),
+        };
+        require!(!paused, msg);
+    }
+}
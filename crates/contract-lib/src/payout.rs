@@ -0,0 +1,138 @@
+use crate::{
+    Contract, ContractExt, Event, ProtectedWithdrawal, GAS_FOR_RESOLVE_OWNER_PAYOUT,
+    GAS_FOR_RESOLVE_PAYOUT,
+};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_sdk::{
+    assert_one_yocto, env, json_types::U128, near, near_bindgen, require, AccountId, NearToken,
+    Promise, PromiseResult,
+};
+
+/// Internal balance, that an owner payout has been taken from.
+#[near(serializers = [json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayoutSource {
+    Deposit,
+    Reward,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Retries a payout of `token_id` that previously failed for the caller.
+    #[payable]
+    pub fn claim_pending(&mut self, token_id: AccountId) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let pending = self
+            .pending_payouts
+            .get_mut(&account_id)
+            .unwrap_or_else(|| env::panic_str("No pending payouts found"));
+        let amount = pending
+            .remove(&token_id)
+            .unwrap_or_else(|| env::panic_str("No pending payout found for token"));
+        if pending.is_empty() {
+            self.pending_payouts.remove(&account_id);
+        }
+
+        Self::payout_transfer(token_id, account_id, amount)
+    }
+
+    #[private]
+    pub fn on_payout(&mut self, receiver_id: AccountId, token_id: AccountId, amount: U128) -> bool {
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                let pending = self
+                    .pending_payouts
+                    .entry(receiver_id)
+                    .or_default()
+                    .entry(token_id)
+                    .or_default();
+                *pending += amount.0;
+                false
+            }
+        }
+    }
+
+    #[private]
+    pub fn on_owner_payout(
+        &mut self,
+        source: PayoutSource,
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        protected: Option<ProtectedWithdrawal>,
+    ) -> bool {
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                // the withdrawal might have burnt the last shares
+                self.internal_collect_dust();
+                true
+            }
+            PromiseResult::Failed => {
+                let balance = match source {
+                    PayoutSource::Deposit => &mut self.deposits,
+                    PayoutSource::Reward => &mut self.rewards,
+                };
+                match balance.get_mut(&token_id) {
+                    Some(balance) => *balance += amount.0,
+                    None => {
+                        balance.insert(token_id.clone(), amount.0);
+                    }
+                }
+                if let Some(protected) = protected {
+                    self.internal_restore_protected_withdrawal(&token_id, protected);
+                }
+
+                Event::OwnerPayoutFailed {
+                    source,
+                    token_id,
+                    receiver_id,
+                    amount,
+                }
+                .emit();
+                false
+            }
+        }
+    }
+}
+
+impl Contract {
+    /// Transfers `amount` of `token_id` to `receiver_id` and keeps track of it, if the transfer fails.
+    pub(crate) fn payout_transfer(
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: u128,
+    ) -> Promise {
+        require!(amount > 0, "The amount should be a positive number");
+        ext_ft_core::ext(token_id.clone())
+            .with_unused_gas_weight(1)
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .ft_transfer(receiver_id.clone(), amount.into(), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_PAYOUT)
+                    .on_payout(receiver_id, token_id, amount.into()),
+            )
+    }
+
+    /// Transfers `amount` of `token_id`, that has been taken from `source`,
+    /// and restores it together with the holder protection state, if the transfer fails.
+    pub(crate) fn owner_payout_transfer(
+        source: PayoutSource,
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: u128,
+        protected: Option<ProtectedWithdrawal>,
+    ) -> Promise {
+        ext_ft_core::ext(token_id.clone())
+            .with_unused_gas_weight(1)
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .ft_transfer(receiver_id.clone(), amount.into(), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_OWNER_PAYOUT)
+                    .on_owner_payout(source, token_id, receiver_id, amount.into(), protected),
+            )
+    }
+}
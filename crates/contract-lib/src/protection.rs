@@ -0,0 +1,249 @@
+use crate::{Contract, ContractExt, Event, PausableFeature};
+use near_contract_standards::fungible_token::events::{FtBurn, FtMint};
+use near_sdk::{
+    env,
+    json_types::{U128, U64},
+    near, near_bindgen, require, AccountId,
+};
+use primitive_types::U256;
+
+/// 2 days
+pub const DEFAULT_WITHDRAWAL_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Whether owner withdrawals of rewards are capped to protect holders.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HolderProtection {
+    #[default]
+    Disabled,
+    /// The owner can only withdraw rewards up to its own pro-rata entitlement,
+    /// unless a withdrawal has been announced and the withdrawal delay has passed.
+    Enabled,
+    /// Holder protection stays enabled until `disabled_at`.
+    Disabling { disabled_at: U64 },
+}
+
+/// A reward withdrawal above the owner's entitlement, that has been announced by the owner.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug)]
+pub struct AnnouncedWithdrawal {
+    pub amount: U128,
+    /// Timestamp in nanoseconds after which the withdrawal can be executed.
+    pub executable_at: U64,
+}
+
+/// Changes of a reward withdrawal to the holder protection state, that are reverted if the payout fails.
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub struct ProtectedWithdrawal {
+    /// Owner shares, that have been burnt for a withdrawal within the owner's entitlement.
+    pub burnt_shares: U128,
+    /// Announced withdrawal, that has been executed.
+    pub withdrawal: Option<AnnouncedWithdrawal>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Enabling takes effect immediately.
+    /// Disabling only takes effect after the withdrawal delay, so that holders can burn their shares first.
+    pub fn set_holder_protection(&mut self, enabled: bool) {
+        self.require_owner();
+        if !enabled {
+            self.require_not_paused(PausableFeature::Burn);
+        }
+        self.holder_protection = match (enabled, self.holder_protection) {
+            (true, HolderProtection::Enabled) => {
+                env::panic_str("Holder protection is already enabled")
+            }
+            (true, _) => HolderProtection::Enabled,
+            (false, HolderProtection::Enabled) => HolderProtection::Disabling {
+                disabled_at: U64(env::block_timestamp() + self.withdrawal_delay),
+            },
+            (false, _) => env::panic_str("Holder protection is not enabled"),
+        };
+
+        Event::HolderProtectionUpdated {
+            protection: self.holder_protection,
+        }
+        .emit();
+    }
+
+    /// Sets the delay in nanoseconds between announcing and executing a reward withdrawal.
+    /// It can't be reduced while holder protection is enabled.
+    pub fn set_withdrawal_delay(&mut self, delay: U64) {
+        self.require_owner();
+        require!(
+            !self.is_holder_protected() || delay.0 >= self.withdrawal_delay,
+            "Can't reduce the withdrawal delay while holder protection is enabled"
+        );
+        self.withdrawal_delay = delay.0;
+
+        Event::WithdrawalDelayUpdated { delay }.emit();
+    }
+
+    /// Announces a withdrawal of `amount` rewards of `token_id` above the owner's entitlement.
+    /// It can be executed via `withdraw_reward` or `remove_reward` once the withdrawal delay has passed.
+    /// Announcing another withdrawal of the same token replaces the pending one and restarts the delay.
+    pub fn announce_reward_withdrawal(&mut self, token_id: AccountId, amount: U128) {
+        self.require_owner();
+        self.require_not_paused(PausableFeature::Burn);
+        require!(amount.0 > 0, "amount must be positive");
+        require!(
+            self.rewards
+                .get(&token_id)
+                .is_some_and(|reward| *reward > 0),
+            "No reward found for token"
+        );
+        let executable_at = U64(env::block_timestamp() + self.withdrawal_delay);
+        self.reward_withdrawals.insert(
+            token_id.clone(),
+            AnnouncedWithdrawal {
+                amount,
+                executable_at,
+            },
+        );
+
+        Event::RewardWithdrawalAnnounced {
+            token_id,
+            amount,
+            executable_at,
+        }
+        .emit();
+    }
+
+    pub fn cancel_reward_withdrawal(&mut self, token_id: AccountId) {
+        self.require_owner();
+        let withdrawal = self
+            .reward_withdrawals
+            .remove(&token_id)
+            .unwrap_or_else(|| env::panic_str("No reward withdrawal announced for token"));
+
+        Event::RewardWithdrawalCancelled {
+            token_id,
+            amount: withdrawal.amount,
+        }
+        .emit();
+    }
+}
+
+impl Contract {
+    pub(crate) fn is_holder_protected(&self) -> bool {
+        match self.holder_protection {
+            HolderProtection::Disabled => false,
+            HolderProtection::Enabled => true,
+            HolderProtection::Disabling { disabled_at } => env::block_timestamp() < disabled_at.0,
+        }
+    }
+
+    /// Returns the part of the rewards of `token_id`, that is backed by the owner's shares.
+    pub(crate) fn owner_entitlement(&self, token_id: &AccountId) -> u128 {
+        let reward = self.rewards.get(token_id).copied().unwrap_or_default();
+        let owner_shares = self.accounts.get(&self.owner).copied().unwrap_or_default();
+        self.share_of(owner_shares, reward)
+    }
+
+    /// Checks, that minting `shares` for the mintable `deposits` doesn't lower the value of a share
+    /// in any reward token while holder protection is enabled.
+    /// Otherwise the owner could raise its entitlement by minting shares, that aren't backed by new rewards.
+    pub(crate) fn require_value_preserving_mint(
+        &self,
+        deposits: &[(AccountId, u128)],
+        shares: u128,
+    ) {
+        if shares == 0 || self.shares == 0 || !self.is_holder_protected() {
+            return;
+        }
+        let supply = U256::from(self.shares);
+        let new_supply = U256::from(self.shares + shares);
+        for (token_id, reward) in self.rewards.iter() {
+            let deposit = deposits
+                .iter()
+                .find(|(deposit_token_id, _)| deposit_token_id == token_id)
+                .map_or(0, |(_, amount)| *amount);
+            require!(
+                U256::from(*reward + deposit) * supply >= U256::from(*reward) * new_supply,
+                "Mint would lower the share value while holder protection is enabled"
+            );
+        }
+    }
+
+    /// Checks a withdrawal of `amount` rewards of `token_id` against the holder protection.
+    /// Withdrawals up to the owner's entitlement burn the owner's shares backing them,
+    /// so that the value of all other shares stays the same.
+    /// Larger withdrawals need an announced withdrawal, that has passed the withdrawal delay.
+    pub(crate) fn internal_protect_reward_withdrawal(
+        &mut self,
+        token_id: &AccountId,
+        amount: u128,
+    ) -> Option<ProtectedWithdrawal> {
+        if !self.is_holder_protected() {
+            return None;
+        }
+        if amount <= self.owner_entitlement(token_id) {
+            let reward = self.rewards.get(token_id).copied().unwrap();
+            // rounded up, so that the remaining shares are never worth less
+            let shares = (U256::from(amount) * U256::from(self.shares) + U256::from(reward - 1))
+                / U256::from(reward);
+            let shares = shares.as_u128();
+            let owner_id = self.owner.clone();
+            let owner_shares = self.accounts.get(&owner_id).copied().unwrap();
+            self.internal_set_balance(&owner_id, owner_shares - shares);
+            self.shares -= shares;
+            FtBurn {
+                owner_id: &self.owner,
+                amount: U128(shares),
+                memo: Some("Reward withdrawal"),
+            }
+            .emit();
+            return Some(ProtectedWithdrawal {
+                burnt_shares: U128(shares),
+                withdrawal: None,
+            });
+        }
+
+        let withdrawal = self
+            .reward_withdrawals
+            .remove(token_id)
+            .unwrap_or_else(|| env::panic_str("Withdrawal exceeds the owner's entitlement"));
+        require!(
+            env::block_timestamp() >= withdrawal.executable_at.0,
+            "Withdrawal delay has not passed yet"
+        );
+        require!(
+            amount <= withdrawal.amount.0,
+            "Withdrawal exceeds the announced amount"
+        );
+        Some(ProtectedWithdrawal {
+            burnt_shares: U128(0),
+            withdrawal: Some(withdrawal),
+        })
+    }
+
+    /// Reverts a reward withdrawal, whose payout has failed.
+    /// The burnt shares are minted to the owner again and the announced withdrawal is restored,
+    /// unless another withdrawal of `token_id` has been announced in the meantime.
+    pub(crate) fn internal_restore_protected_withdrawal(
+        &mut self,
+        token_id: &AccountId,
+        protected: ProtectedWithdrawal,
+    ) {
+        let shares = protected.burnt_shares.0;
+        if shares > 0 {
+            let owner_id = self.owner.clone();
+            let owner_shares = self.accounts.get(&owner_id).copied().unwrap();
+            self.internal_set_balance(&owner_id, owner_shares + shares);
+            self.shares += shares;
+            FtMint {
+                owner_id: &owner_id,
+                amount: U128(shares),
+                memo: Some("Reward withdrawal failed"),
+            }
+            .emit();
+        }
+        if let Some(withdrawal) = protected.withdrawal {
+            if !self.reward_withdrawals.contains_key(token_id) {
+                self.reward_withdrawals.insert(token_id.clone(), withdrawal);
+            }
+        }
+    }
+}
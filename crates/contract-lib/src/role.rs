@@ -0,0 +1,69 @@
+use crate::{Contract, ContractExt, Event};
+use near_sdk::{env, near, near_bindgen, require, AccountId};
+
+/// Roles that can be granted by the owner in addition to the owner itself.
+/// The owner implicitly has every role.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    /// Manages the token whitelist, sponsors and pending deposits.
+    Operator,
+    /// Pauses and unpauses the contract.
+    Pauser,
+    /// Upgrades the contract code.
+    Upgrader,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn grant_role(&mut self, account_id: AccountId, role: Role) {
+        self.require_owner();
+        let granted = self
+            .roles
+            .entry(account_id.clone())
+            .or_default()
+            .insert(role);
+        require!(granted, "Account already has this role");
+
+        Event::RoleGranted { account_id, role }.emit();
+    }
+
+    pub fn revoke_role(&mut self, account_id: AccountId, role: Role) {
+        self.require_owner();
+        let roles = self
+            .roles
+            .get_mut(&account_id)
+            .unwrap_or_else(|| env::panic_str("Account has no roles"));
+        require!(roles.remove(&role), "Account doesn't have this role");
+        if roles.is_empty() {
+            self.roles.remove(&account_id);
+        }
+
+        Event::RoleRevoked { account_id, role }.emit();
+    }
+}
+
+impl Contract {
+    pub(crate) fn require_owner(&self) {
+        require!(
+            env::predecessor_account_id() == self.owner,
+            "Only owner can call this function"
+        );
+    }
+
+    pub(crate) fn require_role(&self, role: Role) {
+        require!(
+            self.has_role(&env::predecessor_account_id(), role),
+            format!("Only owner or {:?} can call this function", role)
+        );
+    }
+
+    /// The owner implicitly has every role.
+    pub(crate) fn has_role(&self, account_id: &AccountId, role: Role) -> bool {
+        account_id == &self.owner
+            || self
+                .roles
+                .get(account_id)
+                .is_some_and(|roles| roles.contains(&role))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::Contract;
+use near_sdk::{
+    env,
+    json_types::{U128, U64},
+    near, AccountId,
+};
+
+/// State of the share value right after a `mint`.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug)]
+pub struct MintSnapshot {
+    /// Block timestamp in nanoseconds.
+    pub timestamp: U64,
+    pub shares_minted: U128,
+    pub total_shares: U128,
+    /// Wrapped token amounts backing all shares.
+    pub rewards: Vec<(AccountId, U128)>,
+}
+
+impl Contract {
+    pub(crate) fn record_mint_snapshot(&mut self, shares_minted: u128) {
+        let rewards = self
+            .rewards
+            .iter()
+            .map(|(token_id, amount)| (token_id.clone(), U128(*amount)))
+            .collect();
+        self.mint_snapshots.push(MintSnapshot {
+            timestamp: U64(env::block_timestamp()),
+            shares_minted: U128(shares_minted),
+            total_shares: U128(self.shares),
+            rewards,
+        });
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{Contract, ContractExt, Event, Role};
+use near_sdk::{env, json_types::U128, near, near_bindgen, require, AccountId, Promise};
+
+/// Amounts of a wrapped token, that a sponsor has deposited.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, Default)]
+pub struct SponsorContribution {
+    /// Deposited, but not minted yet. Can still be refunded.
+    pub pending: U128,
+    /// Added to the rewards by a `mint`.
+    pub minted: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Allows `account_id` to deposit whitelisted tokens via `ft_transfer_call`.
+    pub fn add_sponsor(&mut self, account_id: AccountId) {
+        self.require_role(Role::Operator);
+        require!(
+            self.sponsors.insert(account_id.clone()),
+            "Account is already a sponsor"
+        );
+
+        Event::SponsorAdded { account_id }.emit();
+    }
+
+    /// Rejects further deposits of `account_id`. Its pending deposits can still be minted or refunded.
+    pub fn remove_sponsor(&mut self, account_id: AccountId) {
+        self.require_role(Role::Operator);
+        require!(
+            self.sponsors.remove(&account_id),
+            "Account is not a sponsor"
+        );
+
+        Event::SponsorRemoved { account_id }.emit();
+    }
+
+    /// Sends the pending deposit of `token_id` back to `sponsor_id`.
+    /// Can be called by the sponsor itself or the `Operator`.
+    pub fn refund_sponsor_deposit(
+        &mut self,
+        sponsor_id: AccountId,
+        token_id: AccountId,
+    ) -> Promise {
+        if env::predecessor_account_id() != sponsor_id {
+            self.require_role(Role::Operator);
+        }
+        let contribution = self
+            .sponsor_contributions
+            .get_mut(&sponsor_id)
+            .and_then(|contributions| contributions.get_mut(&token_id))
+            .unwrap_or_else(|| env::panic_str("No sponsor deposit found for token"));
+        let amount = contribution.pending.0;
+        require!(amount > 0, "No pending sponsor deposit found for token");
+        contribution.pending = U128(0);
+
+        let deposit = self.deposits.get_mut(&token_id).unwrap();
+        *deposit -= amount;
+        if *deposit == 0 {
+            self.deposits.remove(&token_id);
+        }
+
+        Event::SponsorDepositRefunded {
+            sponsor_id: sponsor_id.clone(),
+            token_id: token_id.clone(),
+            amount: U128(amount),
+        }
+        .emit();
+        Self::payout_transfer(token_id, sponsor_id, amount)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_sponsor_deposit(
+        &mut self,
+        sponsor_id: AccountId,
+        token_id: AccountId,
+        amount: u128,
+    ) {
+        let contribution = self
+            .sponsor_contributions
+            .entry(sponsor_id)
+            .or_default()
+            .entry(token_id)
+            .or_default();
+        contribution.pending.0 += amount;
+    }
+
+    /// Marks all pending sponsor deposits of `token_id` as minted.
+    pub(crate) fn internal_mint_sponsor_deposits(&mut self, token_id: &AccountId) {
+        for contributions in self.sponsor_contributions.values_mut() {
+            if let Some(contribution) = contributions.get_mut(token_id) {
+                contribution.minted.0 += contribution.pending.0;
+                contribution.pending = U128(0);
+            }
+        }
+    }
+
+    /// Returns the part of the deposit of `token_id`, that can still be refunded to sponsors.
+    pub(crate) fn sponsored_deposit(&self, token_id: &AccountId) -> u128 {
+        self.sponsor_contributions
+            .values()
+            .filter_map(|contributions| contributions.get(token_id))
+            .map(|contribution| contribution.pending.0)
+            .sum()
+    }
+}
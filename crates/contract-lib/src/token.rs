@@ -0,0 +1,156 @@
+use crate::{Contract, ContractExt, Event, Role, GAS_FOR_FT_METADATA, GAS_FOR_RESOLVE_METADATA};
+use near_contract_standards::fungible_token::metadata::{ext_ft_metadata, FungibleTokenMetadata};
+use near_sdk::{env, near, near_bindgen, require, AccountId, Promise, PromiseError};
+
+/// Lifecycle state of a wrapped token.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TokenState {
+    /// Deposits, mints and burn payouts are enabled.
+    #[default]
+    Active,
+    /// Deposits are accepted, but `mint` leaves them pending.
+    /// Rewards that have already been minted are still paid out.
+    DepositOnly,
+    /// No new deposits are accepted. Pending deposits can still be minted
+    /// and rewards are still paid out.
+    Sunset,
+    /// The token is no longer wrapped. It can only be removed,
+    /// once it has neither pending deposits nor rewards.
+    Removed,
+}
+
+impl TokenState {
+    pub fn accepts_deposits(&self) -> bool {
+        matches!(self, Self::Active | Self::DepositOnly)
+    }
+
+    pub fn can_mint(&self) -> bool {
+        matches!(self, Self::Active | Self::Sunset)
+    }
+
+    pub fn pays_out(&self) -> bool {
+        !matches!(self, Self::Removed)
+    }
+}
+
+/// Registry entry of a wrapped token with its cached `ft_metadata`.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, Default)]
+pub struct TokenInfo {
+    pub state: TokenState,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Adds a token to the registry, or reactivates a removed one, and fetches its metadata.
+    pub fn whitelist_add_token(&mut self, token_id: AccountId) -> Promise {
+        self.require_role(Role::Operator);
+        if let Some(token) = self.tokens.get_mut(&token_id) {
+            require!(
+                token.state == TokenState::Removed,
+                "Token already whitelisted"
+            );
+            token.state = TokenState::Active;
+        } else {
+            self.tokens.insert(token_id.clone(), TokenInfo::default());
+        }
+
+        Event::TokenWhitelisted {
+            token_id: token_id.clone(),
+        }
+        .emit();
+        Self::fetch_token_metadata(token_id)
+    }
+
+    pub fn whitelist_remove_token(&mut self, token_id: AccountId) {
+        self.require_role(Role::Operator);
+        self.internal_set_token_state(&token_id, TokenState::Removed);
+
+        Event::TokenUnwhitelisted { token_id }.emit();
+    }
+
+    pub fn set_token_state(&mut self, token_id: AccountId, state: TokenState) {
+        self.require_role(Role::Operator);
+        self.internal_set_token_state(&token_id, state);
+    }
+
+    /// Fetches and caches the `ft_metadata` of a registered token again.
+    pub fn refresh_token_metadata(&mut self, token_id: AccountId) -> Promise {
+        require!(
+            self.tokens.contains_key(&token_id),
+            "Token not found in whitelist"
+        );
+        Self::fetch_token_metadata(token_id)
+    }
+
+    #[private]
+    pub fn on_ft_metadata(
+        &mut self,
+        token_id: AccountId,
+        #[callback_result] metadata: Result<FungibleTokenMetadata, PromiseError>,
+    ) -> bool {
+        let Ok(metadata) = metadata else {
+            return false;
+        };
+        let Some(token) = self.tokens.get_mut(&token_id) else {
+            return false;
+        };
+        token.symbol = Some(metadata.symbol.clone());
+        token.decimals = Some(metadata.decimals);
+
+        Event::TokenMetadataUpdated {
+            token_id,
+            symbol: metadata.symbol,
+            decimals: metadata.decimals,
+        }
+        .emit();
+        true
+    }
+}
+
+impl Contract {
+    pub(crate) fn token_state(&self, token_id: &AccountId) -> TokenState {
+        self.tokens
+            .get(token_id)
+            .map_or(TokenState::Removed, |token| token.state)
+    }
+
+    fn internal_set_token_state(&mut self, token_id: &AccountId, state: TokenState) {
+        if state == TokenState::Removed {
+            require!(
+                self.deposits.get(token_id).copied().unwrap_or_default() == 0,
+                "Token has pending deposits"
+            );
+            require!(
+                self.rewards.get(token_id).copied().unwrap_or_default() == 0,
+                "Token has undistributed rewards"
+            );
+        }
+        let token = self
+            .tokens
+            .get_mut(token_id)
+            .unwrap_or_else(|| env::panic_str("Token not found in whitelist"));
+        require!(token.state != state, "Token already has this state");
+        token.state = state;
+
+        Event::TokenStateUpdated {
+            token_id: token_id.clone(),
+            state,
+        }
+        .emit();
+    }
+
+    pub(crate) fn fetch_token_metadata(token_id: AccountId) -> Promise {
+        ext_ft_metadata::ext(token_id.clone())
+            .with_static_gas(GAS_FOR_FT_METADATA)
+            .ft_metadata()
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_METADATA)
+                    .on_ft_metadata(token_id),
+            )
+    }
+}
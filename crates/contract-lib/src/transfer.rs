@@ -0,0 +1,110 @@
+use crate::{Contract, ContractExt, Event};
+use near_contract_standards::fungible_token::events::FtTransfer;
+use near_sdk::{env, json_types::U128, near, near_bindgen, require, AccountId};
+
+/// Who can transfer shares via `ft_transfer` and `ft_transfer_call`.
+/// The validator can always distribute shares via `ft_transfer`
+/// and the owner can always move shares via `ft_transfer_call`.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransferPolicy {
+    /// No other transfers are allowed.
+    #[default]
+    ValidatorOnly,
+    /// Transfers are allowed, if the sender or the receiver is on the transfer allowlist.
+    Allowlist,
+    /// All NEP-141 transfers are allowed.
+    Open,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn set_transfer_policy(&mut self, policy: TransferPolicy) {
+        self.require_owner();
+        self.transfer_policy = policy;
+
+        Event::TransferPolicyUpdated { policy }.emit();
+    }
+
+    pub fn transfer_allowlist_add(&mut self, account_id: AccountId) {
+        self.require_owner();
+        require!(
+            self.transfer_allowlist.insert(account_id.clone()),
+            "Account is already on the transfer allowlist"
+        );
+
+        Event::TransferAllowlistAdded { account_id }.emit();
+    }
+
+    pub fn transfer_allowlist_remove(&mut self, account_id: AccountId) {
+        self.require_owner();
+        require!(
+            self.transfer_allowlist.remove(&account_id),
+            "Account is not on the transfer allowlist"
+        );
+
+        Event::TransferAllowlistRemoved { account_id }.emit();
+    }
+}
+
+impl Contract {
+    /// Checks the transfer policy for a transfer from `sender_id` to `receiver_id`.
+    /// `privileged` transfers are always allowed and `err` is the error for rejected transfers
+    /// under `TransferPolicy::ValidatorOnly`.
+    pub(crate) fn require_transfer_allowed(
+        &self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        privileged: bool,
+        err: &str,
+    ) {
+        if privileged {
+            return;
+        }
+        match self.transfer_policy {
+            TransferPolicy::ValidatorOnly => env::panic_str(err),
+            TransferPolicy::Allowlist => require!(
+                self.transfer_allowlist.contains(sender_id)
+                    || self.transfer_allowlist.contains(receiver_id),
+                "Sender or receiver must be on the transfer allowlist"
+            ),
+            TransferPolicy::Open => {}
+        }
+    }
+
+    /// Moves `amount` shares between two registered accounts. The total supply stays the same.
+    pub(crate) fn internal_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: u128,
+        memo: Option<&str>,
+    ) {
+        require!(
+            sender_id != receiver_id,
+            "Sender and receiver should be different"
+        );
+        require!(amount > 0, "The amount should be a positive number");
+
+        let sender_balance = self.accounts.get(sender_id).copied().unwrap_or_else(|| {
+            env::panic_str(&format!("The account {} is not registered", sender_id))
+        });
+        require!(
+            sender_balance >= amount,
+            "The account doesn't have enough balance"
+        );
+        let receiver_balance = self.accounts.get(receiver_id).copied().unwrap_or_else(|| {
+            env::panic_str(&format!("The account {} is not registered", receiver_id))
+        });
+        self.internal_set_balance(sender_id, sender_balance - amount);
+        self.internal_set_balance(receiver_id, receiver_balance + amount);
+
+        FtTransfer {
+            old_owner_id: sender_id,
+            new_owner_id: receiver_id,
+            amount: U128(amount),
+            memo,
+        }
+        .emit();
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Contract, ContractExt, Event, PausableFeature, Role, GAS_FOR_MIGRATE, STATE_VERSION};
+use near_sdk::{
+    env,
+    json_types::{Base58CryptoHash, U64},
+    near, near_bindgen, require, CryptoHash, GasWeight, NearToken, Promise,
+};
+
+/// 2 days
+pub const DEFAULT_UPGRADE_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+/// 1 hour
+pub const MIN_UPGRADE_DELAY: u64 = 60 * 60 * 1_000_000_000;
+
+/// A code upgrade, that has been staged by the owner.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug)]
+pub struct StagedUpgrade {
+    /// SHA-256 hash of the code, that will be deployed.
+    pub code_hash: Base58CryptoHash,
+    /// Timestamp in nanoseconds after which the upgrade can be executed.
+    pub executable_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Stages an upgrade to the code with the given SHA-256 hash.
+    /// It can be executed once the upgrade delay has passed.
+    /// Staging another upgrade replaces the pending one and restarts the delay.
+    /// Burns must not be paused, so that holders can burn their shares during the delay.
+    pub fn stage_upgrade(&mut self, code_hash: Base58CryptoHash) {
+        self.require_owner();
+        self.require_not_paused(PausableFeature::Burn);
+        let executable_at = U64(env::block_timestamp() + self.upgrade_delay);
+        self.staged_upgrade = Some(StagedUpgrade {
+            code_hash,
+            executable_at,
+        });
+
+        Event::UpgradeStaged {
+            code_hash,
+            executable_at,
+        }
+        .emit();
+    }
+
+    pub fn cancel_upgrade(&mut self) {
+        self.require_owner();
+        let staged_upgrade = self
+            .staged_upgrade
+            .take()
+            .unwrap_or_else(|| env::panic_str("No upgrade staged"));
+
+        Event::UpgradeCancelled {
+            code_hash: staged_upgrade.code_hash,
+        }
+        .emit();
+    }
+
+    /// Sets the delay in nanoseconds between staging and executing an upgrade.
+    /// It can't be changed while an upgrade is staged and can't be lower than `MIN_UPGRADE_DELAY`,
+    /// so that a staged upgrade can never be executed right away.
+    pub fn set_upgrade_delay(&mut self, delay: U64) {
+        self.require_owner();
+        require!(
+            delay.0 >= MIN_UPGRADE_DELAY,
+            "Upgrade delay is below the minimum"
+        );
+        require!(
+            self.staged_upgrade.is_none(),
+            "Can't change the upgrade delay while an upgrade is staged"
+        );
+        self.upgrade_delay = delay.0;
+
+        Event::UpgradeDelayUpdated { delay }.emit();
+    }
+
+    pub fn upgrade(&mut self) -> Promise {
+        self.require_role(Role::Upgrader);
+
+        let code = self.take_staged_code();
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .as_return()
+    }
+
+    /// Deploys the new code and migrates the state from the current state version.
+    /// Both happen in the same receipt, so a failing migration also reverts the deployment.
+    pub fn upgrade_and_migrate(&mut self) -> Promise {
+        self.require_role(Role::Upgrader);
+        require!(
+            Self::state_version() == STATE_VERSION,
+            "Stored state version doesn't match the contract"
+        );
+
+        let code = self.take_staged_code();
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call_weight(
+                "migrate".to_string(),
+                vec![],
+                NearToken::from_yoctonear(0),
+                GAS_FOR_MIGRATE,
+                GasWeight(1),
+            )
+            .as_return()
+    }
+}
+
+impl Contract {
+    /// Reads the code from the input and checks it against the staged upgrade.
+    fn take_staged_code(&mut self) -> Vec<u8> {
+        let staged_upgrade = self
+            .staged_upgrade
+            .take()
+            .unwrap_or_else(|| env::panic_str("No upgrade staged"));
+        require!(
+            env::block_timestamp() >= staged_upgrade.executable_at.0,
+            "Upgrade delay has not passed yet"
+        );
+
+        let code = env::input().expect("Error: No input").to_vec();
+        require!(
+            env::sha256_array(&code) == CryptoHash::from(staged_upgrade.code_hash),
+            "Code doesn't match the staged upgrade"
+        );
+
+        Event::UpgradeExecuted {
+            code_hash: staged_upgrade.code_hash,
+        }
+        .emit();
+        code
+    }
+}
@@ -1,11 +1,165 @@
-use crate::{Contract, ContractExt};
-use near_sdk::{json_types::U128, near_bindgen, AccountId};
+use crate::{
+    mint::share_value, AnnouncedWithdrawal, Contract, ContractExt, HolderIndex, HolderProtection,
+    MintSnapshot, PauseState, PenaltyConfig, Role, SponsorContribution, StagedUpgrade, TokenInfo,
+    TokenState, TransferPolicy, BPS_DIVISOR,
+};
+use near_sdk::{
+    env,
+    json_types::{U128, U64},
+    near, near_bindgen, require, AccountId,
+};
 use primitive_types::U256;
+use std::ops::Bound;
+
+const MAX_TOP_HOLDERS: u32 = 100;
+
+/// Preview of a burn for a specific account.
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub struct BurnSimulation {
+    /// Shares, that are redeemed for wrapped tokens.
+    pub burnt_shares: U128,
+    /// Shares, that are taken as penalty.
+    pub penalty: U128,
+    /// Wrapped token amounts, that would be paid out.
+    pub payouts: Vec<(AccountId, U128)>,
+    /// Score, that would be tracked for the primary NFT.
+    pub score: U128,
+}
+
+/// Preview of a `mint`.
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub struct MintSimulation {
+    /// Total shares after the mint.
+    pub total_shares: U128,
+    /// Wrapped token amounts per share (10^24) before and after the mint.
+    pub share_values: Vec<ShareValueChange>,
+    /// Part of the total shares after the mint, that is newly minted.
+    /// Existing holders own this much less of the wrapped tokens.
+    pub dilution_bps: u16,
+}
+
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub struct ShareValueChange {
+    pub token_id: AccountId,
+    pub before: U128,
+    pub after: U128,
+}
 
 #[near_bindgen]
 impl Contract {
+    /// Returns registered accounts and their balances ordered by account ID.
+    /// Pagination continues after `from_account`, if given.
+    pub fn get_accounts(
+        &self,
+        from_account: Option<AccountId>,
+        limit: Option<u32>,
+    ) -> Vec<(AccountId, U128)> {
+        let start = from_account
+            .as_ref()
+            .map_or(Bound::Unbounded, Bound::Excluded);
+        self.accounts
+            .range::<_, AccountId>((start, Bound::Unbounded))
+            .take(limit.unwrap_or(u32::MAX) as usize)
+            .map(|(account_id, balance)| (account_id.clone(), U128(*balance)))
+            .collect()
+    }
+
+    pub fn get_account_count(&self) -> u32 {
+        self.accounts.len()
+    }
+
+    /// Returns the number of accounts with a positive balance.
+    pub fn get_holder_count(&self) -> u32 {
+        self.require_holder_index();
+        self.holders.len()
+    }
+
+    /// Returns accounts with a positive balance ordered by balance, highest first.
+    /// Pagination continues after `from_account`, if given. At most `MAX_TOP_HOLDERS` are returned.
+    pub fn get_top_holders(
+        &self,
+        from_account: Option<AccountId>,
+        limit: Option<u32>,
+    ) -> Vec<(AccountId, U128)> {
+        self.require_holder_index();
+        let end = from_account.map_or(Bound::Unbounded, |account_id| {
+            let balance = self.accounts.get(&account_id).copied().unwrap_or_default();
+            Bound::Excluded((balance, account_id))
+        });
+        self.holders
+            .range::<_, (u128, AccountId)>((Bound::Unbounded, end))
+            .rev()
+            .take(limit.unwrap_or(MAX_TOP_HOLDERS).min(MAX_TOP_HOLDERS) as usize)
+            .map(|((balance, account_id), _)| (account_id.clone(), U128(*balance)))
+            .collect()
+    }
+
+    pub fn get_holder_index(&self) -> HolderIndex {
+        self.holder_index.clone()
+    }
+
+    /// Returns all tokens, that haven't been removed from the registry.
     pub fn get_whitelisted_tokens(&self) -> Vec<AccountId> {
-        self.token_whitelist.get().clone()
+        self.tokens
+            .iter()
+            .filter(|(_, token)| token.state != TokenState::Removed)
+            .map(|(token_id, _)| token_id.clone())
+            .collect()
+    }
+
+    pub fn get_tokens(&self) -> Vec<(AccountId, TokenInfo)> {
+        self.tokens
+            .iter()
+            .map(|(token_id, token)| (token_id.clone(), token.clone()))
+            .collect()
+    }
+
+    pub fn get_token(&self, token_id: AccountId) -> Option<TokenInfo> {
+        self.tokens.get(&token_id).cloned()
+    }
+
+    pub fn get_sponsors(&self) -> Vec<AccountId> {
+        self.sponsors.iter().cloned().collect()
+    }
+
+    /// Returns the pending and minted deposits of `sponsor_id` per token.
+    pub fn get_sponsor_contributions(
+        &self,
+        sponsor_id: AccountId,
+    ) -> Vec<(AccountId, SponsorContribution)> {
+        self.sponsor_contributions
+            .get(&sponsor_id)
+            .map(|contributions| {
+                contributions
+                    .iter()
+                    .map(|(token_id, contribution)| (token_id.clone(), contribution.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_holder_protection(&self) -> HolderProtection {
+        self.holder_protection
+    }
+
+    pub fn get_withdrawal_delay(&self) -> U64 {
+        U64(self.withdrawal_delay)
+    }
+
+    pub fn get_reward_withdrawals(&self) -> Vec<(AccountId, AnnouncedWithdrawal)> {
+        self.reward_withdrawals
+            .iter()
+            .map(|(token_id, withdrawal)| (token_id.clone(), withdrawal.clone()))
+            .collect()
+    }
+
+    /// Returns the rewards of `token_id`, that the owner can withdraw without announcement
+    /// while holder protection is enabled.
+    pub fn get_reward_withdrawal_cap(&self, token_id: AccountId) -> U128 {
+        U128(self.owner_entitlement(&token_id))
     }
 
     pub fn get_undistributed_rewards(&self) -> Vec<(AccountId, U128)> {
@@ -22,15 +176,205 @@ impl Contract {
             .collect()
     }
 
+    pub fn get_state_version(&self) -> u32 {
+        Self::state_version()
+    }
+
+    /// Returns the pending upgrade, so that holders can burn their shares before it is executed.
+    pub fn get_staged_upgrade(&self) -> Option<StagedUpgrade> {
+        self.staged_upgrade.clone()
+    }
+
+    pub fn get_upgrade_delay(&self) -> U64 {
+        U64(self.upgrade_delay)
+    }
+
+    pub fn get_owner(&self) -> AccountId {
+        self.owner.clone()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner.clone()
+    }
+
+    pub fn get_transfer_policy(&self) -> TransferPolicy {
+        self.transfer_policy
+    }
+
+    pub fn get_transfer_allowlist(&self) -> Vec<AccountId> {
+        self.transfer_allowlist.iter().cloned().collect()
+    }
+
+    pub fn get_pause_state(&self) -> PauseState {
+        self.paused.clone()
+    }
+
+    /// Returns the snapshots recorded at every `mint`, oldest first.
+    pub fn get_mint_snapshots(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<MintSnapshot> {
+        self.mint_snapshots
+            .iter()
+            .skip(from_index.unwrap_or_default() as usize)
+            .take(limit.unwrap_or(u32::MAX) as usize)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_mint_snapshot_count(&self) -> u32 {
+        self.mint_snapshots.len()
+    }
+
+    pub fn get_roles(&self, account_id: AccountId) -> Vec<Role> {
+        let mut roles: Vec<_> = self
+            .roles
+            .get(&account_id)
+            .map(|roles| roles.iter().copied().collect())
+            .unwrap_or_default();
+        roles.sort();
+        roles
+    }
+
+    pub fn get_role_members(&self) -> Vec<(AccountId, Vec<Role>)> {
+        self.roles
+            .keys()
+            .map(|account_id| (account_id.clone(), self.get_roles(account_id.clone())))
+            .collect()
+    }
+
+    pub fn get_penalty(&self) -> PenaltyConfig {
+        self.penalty.clone()
+    }
+
+    pub fn get_score_multipliers(&self) -> Vec<(AccountId, u32)> {
+        self.score_multipliers
+            .iter()
+            .map(|(token_id, multiplier)| (token_id.clone(), *multiplier))
+            .collect()
+    }
+
+    pub fn get_burnt_rewards(&self) -> Vec<(AccountId, U128)> {
+        self.burnt_rewards
+            .iter()
+            .map(|(token_id, amount)| (token_id.clone(), U128(*amount)))
+            .collect()
+    }
+
+    pub fn get_pending_payouts(&self, account_id: AccountId) -> Vec<(AccountId, U128)> {
+        self.pending_payouts
+            .get(&account_id)
+            .map(|pending| {
+                pending
+                    .iter()
+                    .map(|(token_id, amount)| (token_id.clone(), U128(*amount)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn simulate_burn(&self, shares: U128) -> Vec<(AccountId, U128)> {
         self.rewards
+            .iter()
+            .map(|(token_id, deposit)| (token_id.clone(), U128(self.share_of(shares.0, *deposit))))
+            .collect()
+    }
+
+    /// Simulates `mint(shares)` with the current rewards and the mintable pending deposits.
+    pub fn simulate_mint(&self, shares: U128) -> MintSimulation {
+        let total_shares = self.shares + shares.0;
+        require!(total_shares > 0, "No shares to simulate");
+
+        let mut amounts: Vec<(AccountId, u128, u128)> = self
+            .rewards
+            .iter()
+            .map(|(token_id, reward)| (token_id.clone(), *reward, *reward))
+            .collect();
+        for (token_id, deposit) in self.deposits.iter() {
+            if !self.token_state(token_id).can_mint() {
+                continue;
+            }
+            match amounts.iter_mut().find(|(id, _, _)| id == token_id) {
+                Some((_, _, after)) => *after += deposit,
+                None => amounts.push((token_id.clone(), 0, *deposit)),
+            }
+        }
+
+        let share_values = amounts
+            .into_iter()
+            .map(|(token_id, before, after)| ShareValueChange {
+                token_id,
+                before: U128(if self.shares > 0 {
+                    share_value(before, self.shares)
+                } else {
+                    0
+                }),
+                after: U128(share_value(after, total_shares)),
+            })
+            .collect();
+        let dilution_bps = (U256::from(shares.0) * U256::from(BPS_DIVISOR)
+            / U256::from(total_shares))
+        .as_u32() as u16;
+
+        MintSimulation {
+            total_shares: U128(total_shares),
+            share_values,
+            dilution_bps,
+        }
+    }
+
+    /// Simulates burning `amount` shares (or the whole balance) of `account_id`.
+    /// The penalty and score depend on whether the account has a primary NFT.
+    pub fn simulate_burn_for(
+        &self,
+        account_id: AccountId,
+        amount: Option<U128>,
+        has_primary_nft: bool,
+    ) -> BurnSimulation {
+        let balance = self
+            .accounts
+            .get(&account_id)
+            .copied()
+            .unwrap_or_else(|| env::panic_str("Account has no tokens"));
+        let amount = amount.map_or(balance, |amount| amount.0);
+        require!(amount <= balance, "Not enough tokens to burn");
+
+        // the owner burns without NFT check, so there is neither a penalty nor a score
+        let is_owner = account_id == self.owner;
+        let penalty = if has_primary_nft || is_owner {
+            0
+        } else {
+            self.penalty_of(amount)
+        };
+        let burnt_shares = amount - penalty;
+
+        let mut score = 0;
+        let payouts = self
+            .rewards
             .iter()
             .map(|(token_id, deposit)| {
-                let amount = (U256::from(shares.0) * U256::from(*deposit)
-                    / U256::from(self.shares))
-                .as_u128();
-                (token_id.clone(), U128(amount))
+                let payout = self.share_of(burnt_shares, *deposit);
+                if has_primary_nft && !is_owner {
+                    score += self.score_of(token_id, payout).unwrap_or_default();
+                }
+                (token_id.clone(), U128(payout))
             })
+            .collect();
+
+        BurnSimulation {
+            burnt_shares: U128(burnt_shares),
+            penalty: U128(penalty),
+            payouts,
+            score: U128(score),
+        }
+    }
+
+    /// Returns the rewards, that were left over after all shares have been burnt.
+    pub fn get_dust(&self) -> Vec<(AccountId, U128)> {
+        self.dust
+            .iter()
+            .map(|(token_id, amount)| (token_id.clone(), U128(*amount)))
             .collect()
     }
 }
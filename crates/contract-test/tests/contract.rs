@@ -1,7 +1,12 @@
 mod util;
 
+use contract_lib::{HolderIndex, PayoutSource, TokenState};
 use futures::future::try_join_all;
-use near_sdk::{json_types::U128, NearToken};
+use near_sdk::{
+    json_types::{Base58CryptoHash, U128},
+    serde_json::json,
+    NearToken,
+};
 use util::*;
 
 #[tokio::test]
@@ -147,6 +152,7 @@ async fn test_basic_reward_distribution() -> anyhow::Result<()> {
         assert!(farm.active);
         assert!(farm.start_date.0 > worker.view_block().await?.timestamp());
 
+        call::storage_deposit(&contract, &council, None, None).await?;
         call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
         let account = view::get_account(&pool_contract, council.id()).await?;
         assert_eq!(
@@ -237,6 +243,7 @@ async fn test_basic_reward_distribution() -> anyhow::Result<()> {
             contract.id(),
             &old_rewards[0].0,
             old_rewards[0].1 .0 / 2,
+            None,
         )
         .await?;
         call::act_proposal(
@@ -252,6 +259,7 @@ async fn test_basic_reward_distribution() -> anyhow::Result<()> {
             contract.id(),
             &old_rewards[1].0,
             old_rewards[1].1 .0,
+            None,
         )
         .await?;
         call::act_proposal(
@@ -486,6 +494,7 @@ async fn test_multiple_users_reward_distribution() -> anyhow::Result<()> {
         assert!(farm.active);
         assert!(farm.start_date.0 > worker.view_block().await?.timestamp());
 
+        call::storage_deposit(&contract, &user_a, None, None).await?;
         call::deposit_and_stake(&user_a, pool_contract.id(), NearToken::from_near(10_000)).await?;
         let account = view::get_account(&pool_contract, user_a.id()).await?;
         assert_eq!(
@@ -493,6 +502,7 @@ async fn test_multiple_users_reward_distribution() -> anyhow::Result<()> {
             NearToken::from_near(10_000).as_yoctonear()
         );
 
+        call::storage_deposit(&contract, &user_b, None, None).await?;
         call::deposit_and_stake(&user_b, pool_contract.id(), NearToken::from_near(1_000)).await?;
         let account = view::get_account(&pool_contract, user_b.id()).await?;
         assert_eq!(
@@ -759,6 +769,7 @@ async fn test_refresh_rewards_when_active() -> anyhow::Result<()> {
             assert!(farm.active);
             assert!(farm.start_date.0 > worker.view_block().await?.timestamp());
 
+            call::storage_deposit(&contract, &council, None, None).await?;
             call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000))
                 .await?;
             let account = view::get_account(&pool_contract, council.id()).await?;
@@ -1059,6 +1070,7 @@ async fn test_refresh_rewards_when_inactive() -> anyhow::Result<()> {
             assert!(farm.active);
             assert!(farm.start_date.0 > worker.view_block().await?.timestamp());
 
+            call::storage_deposit(&contract, &council, None, None).await?;
             call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000))
                 .await?;
             let account = view::get_account(&pool_contract, council.id()).await?;
@@ -1327,6 +1339,7 @@ async fn test_no_nft_penalty() -> anyhow::Result<()> {
         assert!(farm.active);
         assert!(farm.start_date.0 > worker.view_block().await?.timestamp());
 
+        call::storage_deposit(&contract, &council, None, None).await?;
         call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
         let account = view::get_account(&pool_contract, council.id()).await?;
         assert_eq!(
@@ -1563,6 +1576,7 @@ async fn test_mint_additional_shares() -> anyhow::Result<()> {
         assert!(farm.active);
         assert!(farm.start_date.0 > worker.view_block().await?.timestamp());
 
+        call::storage_deposit(&contract, &council, None, None).await?;
         call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
         let account = view::get_account(&pool_contract, council.id()).await?;
         assert_eq!(
@@ -1635,6 +1649,2895 @@ async fn test_mint_additional_shares() -> anyhow::Result<()> {
         let balance = view::ft_balance_of(&contract, pool_contract.id()).await?;
         assert_eq!(balance.0, shares - council_balance.0);
 
+        let snapshots = view::get_mint_snapshots(&contract, None, None).await?;
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].total_shares.0, shares);
+        assert_eq!(snapshots[1].shares_minted.0, shares);
+        assert_eq!(snapshots[1].total_shares.0, shares * 2);
+        assert!(snapshots[0].timestamp.0 < snapshots[1].timestamp.0);
+        for (_, amount) in &snapshots[1].rewards {
+            assert_eq!(amount.0, mint_amount * 2);
+        }
+        let snapshots = view::get_mint_snapshots(&contract, Some(1), Some(1)).await?;
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].total_shares.0, shares * 2);
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_partial_burn() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            worker,
+            council,
+            contract,
+            dao_contract,
+            pool_contract,
+            nft_contract,
+            rewarder_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let mint_amount = 1_000_000;
+
+        call::nft_mint(&council, nft_contract.id(), 1).await?;
+        let [token] = &view::nft_tokens_for_owner(&nft_contract, council.id()).await?[..] else {
+            return Err(anyhow::anyhow!("No NFT tokens"));
+        };
+        call::stake_nft_with_rewarder(
+            &council,
+            nft_contract.id(),
+            rewarder_contract.id(),
+            &token.token_id,
+        )
+        .await?;
+
+        let (proposal_id, _) = call::propose_add_authorized_farm_token(
+            &council,
+            dao_contract.id(),
+            pool_contract.id(),
+            contract.id(),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        try_join_all(token_contracts.iter().cloned().map(|token_contract| {
+            let council = council.clone();
+            let contract = contract.clone();
+            let dao_contract = dao_contract.clone();
+            tokio::spawn(async move {
+                call::storage_deposit(&token_contract, &council, None, None).await?;
+                call::storage_deposit(&token_contract, &council, Some(contract.id()), None).await?;
+                call::storage_deposit(&token_contract, &council, Some(dao_contract.id()), None)
+                    .await?;
+                call::mint_tokens(&token_contract, dao_contract.id(), mint_amount).await?;
+
+                let (proposal_id, _) = call::propose_deposit_tokens(
+                    &council,
+                    dao_contract.id(),
+                    token_contract.id(),
+                    contract.id(),
+                    mint_amount,
+                )
+                .await?;
+                call::act_proposal(
+                    &council,
+                    dao_contract.id(),
+                    proposal_id,
+                    Action::VoteApprove,
+                )
+                .await?;
+                anyhow::Ok(())
+            })
+        }))
+        .await?;
+
+        let shares = NearToken::from_near(1).as_yoctonear();
+
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), shares).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        let block = worker.view_block().await?;
+        let start_date = block.timestamp() + 1_000_000_000 * 60; // 1min
+        let end_date = block.timestamp() + 1_000_000_000 * 60 * 5; // 5min
+        let (proposal_id, _) = call::propose_create_farm(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            pool_contract.id(),
+            shares,
+            "Dogshit".to_string(),
+            start_date,
+            end_date,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        call::storage_deposit(&contract, &council, None, None).await?;
+        call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
+
+        while worker.view_block().await?.timestamp() < start_date {
+            worker.fast_forward(5).await?;
+        }
+        worker.fast_forward(5).await?;
+
+        call::claim(&council, pool_contract.id(), contract.id()).await?;
+        let balance = view::ft_balance_of(&contract, council.id()).await?;
+        assert!(balance.0 > 1);
+
+        let burn_amount = balance.0 / 2;
+        let (burnt_shares, _) = call::burn_amount(&council, contract.id(), burn_amount).await?;
+        assert_eq!(burnt_shares.0, burn_amount);
+        let remaining = view::ft_balance_of(&contract, council.id()).await?;
+        assert_eq!(remaining.0, balance.0 - burn_amount);
+        let total_supply = view::ft_total_supply(&contract).await?;
+        assert_eq!(total_supply.0, shares - burn_amount);
+
+        let distributed = (burn_amount * mint_amount) / shares;
+        for (_, amount) in view::get_undistributed_rewards(&contract).await? {
+            assert_eq!(amount.0, mint_amount - distributed);
+        }
+        for token_contract in &token_contracts {
+            let balance = view::ft_balance_of(token_contract, council.id()).await?;
+            assert_eq!(balance.0, distributed);
+        }
+
+        assert!(call::burn_amount(&council, contract.id(), remaining.0 + 1)
+            .await
+            .is_err());
+
+        // redeem the rest into the DAO account
+        let rewards = view::get_undistributed_rewards(&contract).await?;
+        let total_supply = view::ft_total_supply(&contract).await?;
+        let (burnt_shares, _) =
+            call::burn_to(&council, contract.id(), dao_contract.id(), None).await?;
+        assert_eq!(burnt_shares.0, remaining.0);
+        let balance = view::ft_balance_of(&contract, council.id()).await?;
+        assert_eq!(balance.0, 0);
+        for (token_id, amount) in rewards {
+            let token_contract = token_contracts
+                .iter()
+                .find(|token_contract| token_contract.id() == &token_id)
+                .unwrap();
+            let balance = view::ft_balance_of(token_contract, council.id()).await?;
+            assert_eq!(balance.0, distributed);
+            let balance = view::ft_balance_of(token_contract, dao_contract.id()).await?;
+            assert_eq!(balance.0, (remaining.0 * amount.0) / total_supply.0);
+        }
+
+        // burnt accounts stay registered until they unregister themselves
+        assert!(view::storage_balance_of(&contract, council.id())
+            .await?
+            .is_some());
+        let (unregistered, _) = call::storage_unregister(&contract, &council, None).await?;
+        assert!(unregistered);
+        assert!(view::storage_balance_of(&contract, council.id())
+            .await?
+            .is_none());
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_burn_selected_tokens() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            worker,
+            council,
+            contract,
+            dao_contract,
+            pool_contract,
+            nft_contract,
+            rewarder_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let mint_amount = 1_000_000;
+
+        call::nft_mint(&council, nft_contract.id(), 1).await?;
+        let [token] = &view::nft_tokens_for_owner(&nft_contract, council.id()).await?[..] else {
+            return Err(anyhow::anyhow!("No NFT tokens"));
+        };
+        call::stake_nft_with_rewarder(
+            &council,
+            nft_contract.id(),
+            rewarder_contract.id(),
+            &token.token_id,
+        )
+        .await?;
+
+        let (proposal_id, _) = call::propose_add_authorized_farm_token(
+            &council,
+            dao_contract.id(),
+            pool_contract.id(),
+            contract.id(),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        try_join_all(token_contracts.iter().cloned().map(|token_contract| {
+            let council = council.clone();
+            let contract = contract.clone();
+            let dao_contract = dao_contract.clone();
+            tokio::spawn(async move {
+                call::storage_deposit(&token_contract, &council, None, None).await?;
+                call::storage_deposit(&token_contract, &council, Some(contract.id()), None).await?;
+                call::storage_deposit(&token_contract, &council, Some(dao_contract.id()), None)
+                    .await?;
+                call::mint_tokens(&token_contract, dao_contract.id(), mint_amount).await?;
+
+                let (proposal_id, _) = call::propose_deposit_tokens(
+                    &council,
+                    dao_contract.id(),
+                    token_contract.id(),
+                    contract.id(),
+                    mint_amount,
+                )
+                .await?;
+                call::act_proposal(
+                    &council,
+                    dao_contract.id(),
+                    proposal_id,
+                    Action::VoteApprove,
+                )
+                .await?;
+                anyhow::Ok(())
+            })
+        }))
+        .await?;
+
+        let shares = NearToken::from_near(1).as_yoctonear();
+
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), shares).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        let block = worker.view_block().await?;
+        let start_date = block.timestamp() + 1_000_000_000 * 60; // 1min
+        let end_date = block.timestamp() + 1_000_000_000 * 60 * 5; // 5min
+        let (proposal_id, _) = call::propose_create_farm(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            pool_contract.id(),
+            shares,
+            "Dogshit".to_string(),
+            start_date,
+            end_date,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        call::storage_deposit(&contract, &council, None, None).await?;
+        call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
+
+        while worker.view_block().await?.timestamp() < start_date {
+            worker.fast_forward(5).await?;
+        }
+        worker.fast_forward(5).await?;
+
+        call::claim(&council, pool_contract.id(), contract.id()).await?;
+        let balance = view::ft_balance_of(&contract, council.id()).await?;
+        assert!(balance.0 > 1);
+
+        let (burnt_shares, _) = call::burn_tokens(
+            &council,
+            contract.id(),
+            &[token_contracts[0].id().clone()],
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(burnt_shares.0, balance.0);
+        let total_supply = view::ft_total_supply(&contract).await?;
+        assert_eq!(total_supply.0, shares - balance.0);
+
+        // only the selected token is redeemed, the rest stays with the remaining holders
+        let distributed = (balance.0 * mint_amount) / shares;
+        for (token_id, amount) in view::get_undistributed_rewards(&contract).await? {
+            if &token_id == token_contracts[0].id() {
+                assert_eq!(amount.0, mint_amount - distributed);
+            } else {
+                assert_eq!(amount.0, mint_amount);
+            }
+        }
+        let balance = view::ft_balance_of(&token_contracts[0], council.id()).await?;
+        assert_eq!(balance.0, distributed);
+        for token_contract in &token_contracts[1..] {
+            let balance = view::ft_balance_of(token_contract, council.id()).await?;
+            assert_eq!(balance.0, 0);
+        }
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_pending_payouts() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            worker,
+            near,
+            council,
+            contract,
+            dao_contract,
+            pool_contract,
+            nft_contract,
+            rewarder_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let mint_amount = 1_000_000;
+
+        call::nft_mint(&council, nft_contract.id(), 1).await?;
+        let [token] = &view::nft_tokens_for_owner(&nft_contract, council.id()).await?[..] else {
+            return Err(anyhow::anyhow!("No NFT tokens"));
+        };
+        call::stake_nft_with_rewarder(
+            &council,
+            nft_contract.id(),
+            rewarder_contract.id(),
+            &token.token_id,
+        )
+        .await?;
+
+        let (proposal_id, _) = call::propose_add_authorized_farm_token(
+            &council,
+            dao_contract.id(),
+            pool_contract.id(),
+            contract.id(),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        try_join_all(token_contracts.iter().cloned().map(|token_contract| {
+            let council = council.clone();
+            let contract = contract.clone();
+            let dao_contract = dao_contract.clone();
+            tokio::spawn(async move {
+                call::storage_deposit(&token_contract, &council, None, None).await?;
+                call::storage_deposit(&token_contract, &council, Some(contract.id()), None).await?;
+                call::storage_deposit(&token_contract, &council, Some(dao_contract.id()), None)
+                    .await?;
+                call::mint_tokens(&token_contract, dao_contract.id(), mint_amount).await?;
+
+                let (proposal_id, _) = call::propose_deposit_tokens(
+                    &council,
+                    dao_contract.id(),
+                    token_contract.id(),
+                    contract.id(),
+                    mint_amount,
+                )
+                .await?;
+                call::act_proposal(
+                    &council,
+                    dao_contract.id(),
+                    proposal_id,
+                    Action::VoteApprove,
+                )
+                .await?;
+                anyhow::Ok(())
+            })
+        }))
+        .await?;
+
+        let shares = NearToken::from_near(1).as_yoctonear();
+
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), shares).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        let block = worker.view_block().await?;
+        let start_date = block.timestamp() + 1_000_000_000 * 60; // 1min
+        let end_date = block.timestamp() + 1_000_000_000 * 60 * 5; // 5min
+        let (proposal_id, _) = call::propose_create_farm(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            pool_contract.id(),
+            shares,
+            "Dogshit".to_string(),
+            start_date,
+            end_date,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        call::storage_deposit(&contract, &council, None, None).await?;
+        call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
+
+        while worker.view_block().await?.timestamp() < start_date {
+            worker.fast_forward(5).await?;
+        }
+        worker.fast_forward(5).await?;
+
+        call::claim(&council, pool_contract.id(), contract.id()).await?;
+        let balance = view::ft_balance_of(&contract, council.id()).await?;
+        assert!(balance.0 > 1);
+
+        // receiver is not registered on any reward token, so all payouts fail
+        let receiver = near
+            .create_subaccount("receiver")
+            .initial_balance(NearToken::from_near(100))
+            .transact()
+            .await?
+            .into_result()?;
+        let rewards = view::get_undistributed_rewards(&contract).await?;
+        let (burnt_shares, _) = call::burn_to(&council, contract.id(), receiver.id(), None).await?;
+        assert_eq!(burnt_shares.0, balance.0);
+
+        let distributed = (balance.0 * mint_amount) / shares;
+        let mut pending = view::get_pending_payouts(&contract, receiver.id()).await?;
+        pending.sort_by_key(|pending| pending.0.clone());
+        assert_eq!(
+            pending,
+            token_contracts
+                .iter()
+                .map(|token_contract| (token_contract.id().clone(), U128(distributed)))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?.len(),
+            rewards.len()
+        );
+
+        for token_contract in &token_contracts {
+            call::storage_deposit(token_contract, &receiver, None, None).await?;
+            let (success, _) =
+                call::claim_pending(&receiver, contract.id(), token_contract.id()).await?;
+            assert!(success);
+            let balance = view::ft_balance_of(token_contract, receiver.id()).await?;
+            assert_eq!(balance.0, distributed);
+        }
+        assert!(view::get_pending_payouts(&contract, receiver.id())
+            .await?
+            .is_empty());
+        assert!(
+            call::claim_pending(&receiver, contract.id(), token_contracts[0].id())
+                .await
+                .is_err()
+        );
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_penalty_redistribution() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            worker,
+            council,
+            contract,
+            dao_contract,
+            pool_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let mint_amount = 1_000_000;
+
+        let (proposal_id, _) = call::propose_add_authorized_farm_token(
+            &council,
+            dao_contract.id(),
+            pool_contract.id(),
+            contract.id(),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        try_join_all(token_contracts.iter().cloned().map(|token_contract| {
+            let council = council.clone();
+            let contract = contract.clone();
+            let dao_contract = dao_contract.clone();
+            tokio::spawn(async move {
+                call::storage_deposit(&token_contract, &council, None, None).await?;
+                call::storage_deposit(&token_contract, &council, Some(contract.id()), None).await?;
+                call::storage_deposit(&token_contract, &council, Some(dao_contract.id()), None)
+                    .await?;
+                call::mint_tokens(&token_contract, dao_contract.id(), mint_amount).await?;
+
+                let (proposal_id, _) = call::propose_deposit_tokens(
+                    &council,
+                    dao_contract.id(),
+                    token_contract.id(),
+                    contract.id(),
+                    mint_amount,
+                )
+                .await?;
+                call::act_proposal(
+                    &council,
+                    dao_contract.id(),
+                    proposal_id,
+                    Action::VoteApprove,
+                )
+                .await?;
+                anyhow::Ok(())
+            })
+        }))
+        .await?;
+
+        let shares = NearToken::from_near(1).as_yoctonear();
+
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), shares).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        let block = worker.view_block().await?;
+        let start_date = block.timestamp() + 1_000_000_000 * 60; // 1min
+        let end_date = block.timestamp() + 1_000_000_000 * 60 * 5; // 5min
+        let (proposal_id, _) = call::propose_create_farm(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            pool_contract.id(),
+            shares,
+            "Dogshit".to_string(),
+            start_date,
+            end_date,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        call::storage_deposit(&contract, &council, None, None).await?;
+        call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
+
+        while worker.view_block().await?.timestamp() < start_date {
+            worker.fast_forward(5).await?;
+        }
+        worker.fast_forward(5).await?;
+
+        call::claim(&council, pool_contract.id(), contract.id()).await?;
+        let balance = view::ft_balance_of(&contract, council.id()).await?;
+        assert!(balance.0 > 1);
+
+        let (proposal_id, _) = call::propose_set_penalty(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            5_000,
+            "Redistribute",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // council has no primary NFT, so half of the burnt shares are taken as penalty
+        let simulation = view::simulate_burn_for(&contract, council.id(), None, false).await?;
+        let (burnt_shares, events) = call::burn(&council, contract.id()).await?;
+        let penalty = balance.0 / 2;
+        assert_eq!(burnt_shares.0, balance.0 - penalty);
+        assert_eq!(simulation.burnt_shares, burnt_shares);
+        assert_eq!(simulation.penalty.0, penalty);
+        assert_eq!(simulation.score.0, 0);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::PenaltyApplied { amount, .. },
+                ..
+            }) if amount.0 == penalty
+        )));
+        let payouts = events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                        event_kind: ValidatorRewardsEventKind::BurnPayout { .. },
+                        ..
+                    })
+                )
+            })
+            .count();
+        assert_eq!(payouts, token_contracts.len());
+        let total_supply = view::ft_total_supply(&contract).await?;
+        assert_eq!(total_supply.0, shares - balance.0);
+        let balance = view::ft_balance_of(&contract, dao_contract.id()).await?;
+        assert_eq!(balance.0, 0);
+
+        // the penalty's part of the wrapped tokens stays with the remaining holders
+        let distributed = (burnt_shares.0 * mint_amount) / shares;
+        for (_, amount) in view::get_undistributed_rewards(&contract).await? {
+            assert_eq!(amount.0, mint_amount - distributed);
+        }
+        for token_contract in &token_contracts {
+            let balance = view::ft_balance_of(token_contract, council.id()).await?;
+            assert_eq!(balance.0, distributed);
+        }
+        for (_, amount) in simulation.payouts {
+            assert_eq!(amount.0, distributed);
+        }
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_score_multiplier() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            nft_contract,
+            rewarder_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let shitzu_contract = &token_contracts[0];
+        let token_contract = &token_contracts[1];
+        let amount = 1_000_000;
+
+        call::nft_mint(&council, nft_contract.id(), 1).await?;
+        let [token] = &view::nft_tokens_for_owner(&nft_contract, council.id()).await?[..] else {
+            return Err(anyhow::anyhow!("No NFT tokens"));
+        };
+        call::stake_nft_with_rewarder(
+            &council,
+            nft_contract.id(),
+            rewarder_contract.id(),
+            &token.token_id,
+        )
+        .await?;
+
+        for token_contract in [shitzu_contract, token_contract] {
+            call::storage_deposit(token_contract, &council, None, None).await?;
+            call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+            call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+            call::mint_tokens(token_contract, dao_contract.id(), amount).await?;
+        }
+        call::storage_deposit(&contract, &council, None, None).await?;
+
+        // mint 1k shares of both tokens and move half of them to the council
+        for (proposal_id, _) in [
+            call::propose_deposit_tokens(
+                &council,
+                dao_contract.id(),
+                token_contract.id(),
+                contract.id(),
+                amount,
+            )
+            .await?,
+            call::propose_deposit_and_mint(
+                &council,
+                dao_contract.id(),
+                shitzu_contract.id(),
+                contract.id(),
+                amount,
+                1_000,
+            )
+            .await?,
+            call::propose_set_transfer_policy(&council, dao_contract.id(), contract.id(), "Open")
+                .await?,
+            call::propose_ft_transfer(
+                &council,
+                dao_contract.id(),
+                contract.id(),
+                council.id(),
+                500,
+            )
+            .await?,
+        ] {
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+        }
+
+        let (proposal_id, _) = call::propose_set_score_multiplier(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            Some(20_000),
+        )
+        .await?;
+        let (_, events) = call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::ScoreMultiplierUpdated {
+                    token_id,
+                    multiplier_bps: Some(20_000),
+                },
+                ..
+            }) if token_id == token_contract.id()
+        )));
+
+        // both tokens are tracked, SHITZU with 3x and the other token with 2x
+        let (primary_nft, _) = view::primary_nft_of(&rewarder_contract, council.id())
+            .await?
+            .unwrap();
+        assert_eq!(primary_nft, token.token_id);
+        let simulation = view::simulate_burn_for(&contract, council.id(), Some(250), true).await?;
+        assert_eq!(simulation.burnt_shares.0, 250);
+        assert_eq!(simulation.penalty.0, 0);
+        assert_eq!(simulation.score.0, amount / 4 * 3 + amount / 4 * 2);
+        call::burn_amount(&council, contract.id(), 250).await?;
+        for token_contract in [shitzu_contract, token_contract] {
+            let balance = view::ft_balance_of(token_contract, council.id()).await?;
+            assert_eq!(balance.0, amount / 4);
+        }
+        let (_, score) = view::primary_nft_of(&rewarder_contract, council.id())
+            .await?
+            .unwrap();
+        assert_eq!(score.0, amount / 4 * 3 + amount / 4 * 2);
+
+        let (proposal_id, _) = call::propose_set_score_multiplier(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            None,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // the other token is still paid out, but no longer tracked
+        call::burn_amount(&council, contract.id(), 250).await?;
+        for token_contract in [shitzu_contract, token_contract] {
+            let balance = view::ft_balance_of(token_contract, council.id()).await?;
+            assert_eq!(balance.0, amount / 2);
+        }
+        let (_, score) = view::primary_nft_of(&rewarder_contract, council.id())
+            .await?
+            .unwrap();
+        assert_eq!(score.0, amount / 2 * 3 + amount / 4 * 2);
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_roles() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let token_id = token_contracts[0].id();
+
+        // only the owner can manage the whitelist by default
+        assert!(call::whitelist_add_token(&council, contract.id(), token_id)
+            .await
+            .is_err());
+
+        let (proposal_id, _) = call::propose_grant_role(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            council.id(),
+            "Operator",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(
+            view::get_roles(&contract, council.id()).await?,
+            vec!["Operator".to_string()]
+        );
+
+        call::whitelist_remove_token(&council, contract.id(), token_id).await?;
+        call::whitelist_add_token(&council, contract.id(), token_id).await?;
+
+        let (proposal_id, _) = call::propose_revoke_role(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            council.id(),
+            "Operator",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(view::get_roles(&contract, council.id()).await?.is_empty());
+
+        assert!(call::whitelist_add_token(&council, contract.id(), token_id)
+            .await
+            .is_err());
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_pause() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            ..
+        } = initialize_contracts().await?;
+
+        assert!(call::pause(&council, contract.id(), "Burn").await.is_err());
+
+        let (proposal_id, _) = call::propose_grant_role(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            council.id(),
+            "Pauser",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // holders need to be able to burn until a staged upgrade can be executed
+        let code_hash = Base58CryptoHash::from([1u8; 32]);
+        let (proposal_id, _) =
+            call::propose_stage_upgrade(&council, dao_contract.id(), contract.id(), code_hash)
+                .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        let err = call::pause(&council, contract.id(), "Burn")
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Can't pause burns while a timelocked change is pending"));
+        call::pause(&council, contract.id(), "Transfer").await?;
+        call::unpause(&council, contract.id(), "Transfer").await?;
+
+        let (proposal_id, _) =
+            call::propose_cancel_upgrade(&council, dao_contract.id(), contract.id()).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        call::pause(&council, contract.id(), "Burn").await?;
+        let pause_state = view::get_pause_state(&contract).await?;
+        assert!(pause_state.burn);
+        assert!(!pause_state.transfer);
+        assert!(!pause_state.deposit);
+
+        // no upgrade can be staged while burns are paused
+        let (proposal_id, _) =
+            call::propose_stage_upgrade(&council, dao_contract.id(), contract.id(), code_hash)
+                .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(view::get_staged_upgrade(&contract).await?.is_none());
+
+        let err = call::burn(&council, contract.id()).await.unwrap_err();
+        assert!(err.to_string().contains("Burning is paused"));
+
+        call::unpause(&council, contract.id(), "Burn").await?;
+        assert!(!view::get_pause_state(&contract).await?.burn);
+
+        // council has no shares to burn, but the burn is no longer rejected as paused
+        let err = call::burn(&council, contract.id()).await.unwrap_err();
+        assert!(!err.to_string().contains("Burning is paused"));
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_ownership_transfer() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            near,
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let (proposal_id, _) =
+            call::propose_owner(&council, dao_contract.id(), contract.id(), council.id()).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(&view::get_owner(&contract).await?, dao_contract.id());
+
+        // only the proposed account can accept
+        assert!(call::accept_owner(&near, contract.id()).await.is_err());
+
+        call::accept_owner(&council, contract.id()).await?;
+        assert_eq!(&view::get_owner(&contract).await?, council.id());
+        assert_eq!(view::ft_balance_of(&contract, council.id()).await?.0, 0);
+
+        // the new owner no longer needs a DAO proposal
+        call::whitelist_remove_token(&council, contract.id(), token_contracts[0].id()).await?;
+        assert!(call::accept_owner(&council, contract.id()).await.is_err());
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_set_validator() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            pool_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let token_contract = &token_contracts[0];
+        let amount = 1_000_000;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+        call::mint_tokens(token_contract, dao_contract.id(), amount).await?;
+
+        // mint 1k shares and move some of them to the current validator
+        for (proposal_id, _) in [
+            call::propose_deposit_and_mint(
+                &council,
+                dao_contract.id(),
+                token_contract.id(),
+                contract.id(),
+                amount,
+                1_000,
+            )
+            .await?,
+            call::propose_set_transfer_policy(&council, dao_contract.id(), contract.id(), "Open")
+                .await?,
+            call::propose_ft_transfer(
+                &council,
+                dao_contract.id(),
+                contract.id(),
+                pool_contract.id(),
+                400,
+            )
+            .await?,
+        ] {
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+        }
+        assert_eq!(
+            view::ft_balance_of(&contract, pool_contract.id()).await?.0,
+            400
+        );
+
+        // the shares of the previous validator are moved back to the owner
+        let (proposal_id, _) =
+            call::propose_set_validator(&council, dao_contract.id(), contract.id(), council.id())
+                .await?;
+        let (_, events) = call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::ValidatorUpdated { .. },
+                ..
+            })
+        )));
+        assert_eq!(
+            view::ft_balance_of(&contract, pool_contract.id()).await?.0,
+            0
+        );
+        assert_eq!(view::ft_balance_of(&contract, council.id()).await?.0, 0);
+        assert_eq!(
+            view::ft_balance_of(&contract, dao_contract.id()).await?.0,
+            1_000
+        );
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 1_000);
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_token_registry() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        // metadata is fetched for all initially whitelisted tokens
+        for (i, token_contract) in token_contracts.iter().enumerate() {
+            let token = view::get_token(&contract, token_contract.id())
+                .await?
+                .unwrap();
+            assert_eq!(token.state, TokenState::Active);
+            assert_eq!(token.symbol, Some(format!("TKN{}", i)));
+            assert_eq!(token.decimals, Some(18));
+        }
+
+        let (proposal_id, _) = call::propose_grant_role(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            council.id(),
+            "Operator",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // sunset tokens don't accept deposits anymore
+        let token_contract = &token_contracts[1];
+        let (_, events) =
+            call::set_token_state(&council, contract.id(), token_contract.id(), "Sunset").await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::TokenStateUpdated {
+                    token_id,
+                    state: TokenState::Sunset,
+                },
+                ..
+            }) if token_id == token_contract.id()
+        )));
+
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+        call::mint_tokens(token_contract, dao_contract.id(), 1_000).await?;
+        let (proposal_id, _) = call::propose_deposit_tokens(
+            &council,
+            dao_contract.id(),
+            token_contract.id(),
+            contract.id(),
+            1_000,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(view::get_deposits(&contract).await?.is_empty());
+
+        // deposits of `DepositOnly` tokens can't be minted until the token is activated
+        let token_contract = &token_contracts[0];
+        call::set_token_state(&council, contract.id(), token_contract.id(), "DepositOnly").await?;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+        call::mint_tokens(token_contract, dao_contract.id(), 1_000).await?;
+        for (proposal_id, _) in [
+            call::propose_deposit_tokens(
+                &council,
+                dao_contract.id(),
+                token_contract.id(),
+                contract.id(),
+                1_000,
+            )
+            .await?,
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), 1_000).await?,
+        ] {
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+        }
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 0);
+        assert_eq!(
+            view::get_deposits(&contract).await?,
+            vec![(token_contract.id().clone(), U128(1_000))]
+        );
+
+        call::set_token_state(&council, contract.id(), token_contract.id(), "Active").await?;
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), 1_000).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 1_000);
+        assert!(view::get_deposits(&contract).await?.is_empty());
+
+        // removed tokens are no longer listed, but can be whitelisted again
+        let token_id = token_contracts[2].id();
+        call::whitelist_remove_token(&council, contract.id(), token_id).await?;
+        assert!(!view::get_whitelisted_tokens(&contract)
+            .await?
+            .contains(token_id));
+        assert_eq!(
+            view::get_token(&contract, token_id).await?.unwrap().state,
+            TokenState::Removed
+        );
+
+        call::whitelist_add_token(&council, contract.id(), token_id).await?;
+        assert!(view::get_whitelisted_tokens(&contract)
+            .await?
+            .contains(token_id));
+        let err = call::whitelist_add_token(&council, contract.id(), token_id)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Token already whitelisted"));
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_sponsored_deposits() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let token_contract = &token_contracts[1];
+        let amount = 1_000;
+        call::storage_deposit(token_contract, &council, None, None).await?;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::mint_tokens(token_contract, council.id(), 2 * amount).await?;
+
+        // deposits of accounts that aren't sponsors are refunded by the token
+        call::ft_transfer_call(&council, token_contract.id(), contract.id(), amount, "").await?;
+        assert!(view::get_deposits(&contract).await?.is_empty());
+
+        let (proposal_id, _) = call::propose_grant_role(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            council.id(),
+            "Operator",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        call::add_sponsor(&council, contract.id(), council.id()).await?;
+
+        call::ft_transfer_call(&council, token_contract.id(), contract.id(), amount, "").await?;
+        assert_eq!(
+            view::get_deposits(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount))]
+        );
+        let [(token_id, contribution)] =
+            &view::get_sponsor_contributions(&contract, council.id()).await?[..]
+        else {
+            panic!("expected a single sponsor contribution");
+        };
+        assert_eq!(token_id, token_contract.id());
+        assert_eq!(contribution.pending.0, amount);
+        assert_eq!(contribution.minted.0, 0);
+
+        // pending deposits can be refunded to the sponsor
+        let (_, events) = call::refund_sponsor_deposit(
+            &council,
+            contract.id(),
+            council.id(),
+            token_contract.id(),
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::SponsorDepositRefunded { amount: refunded, .. },
+                ..
+            }) if refunded.0 == amount
+        )));
+        assert!(view::get_deposits(&contract).await?.is_empty());
+        assert_eq!(
+            view::ft_balance_of(token_contract, council.id()).await?.0,
+            2 * amount
+        );
+
+        // minted deposits are attributed to the sponsor
+        call::ft_transfer_call(&council, token_contract.id(), contract.id(), amount, "").await?;
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), 100).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        let contributions = view::get_sponsor_contributions(&contract, council.id()).await?;
+        assert_eq!(contributions[0].1.pending.0, 0);
+        assert_eq!(contributions[0].1.minted.0, amount);
+        assert!(call::refund_sponsor_deposit(
+            &council,
+            contract.id(),
+            council.id(),
+            token_contract.id(),
+        )
+        .await
+        .is_err());
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_deposit_and_mint() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let token_contract = &token_contracts[0];
+        let amount = 1_000_000;
+        let shares = 1_000;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+        call::mint_tokens(token_contract, dao_contract.id(), amount).await?;
+        let total_supply = view::ft_total_supply(&contract).await?.0;
+
+        let (proposal_id, _) = call::propose_deposit_and_mint(
+            &council,
+            dao_contract.id(),
+            token_contract.id(),
+            contract.id(),
+            amount,
+            shares,
+        )
+        .await?;
+        let (_, events) = call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::RewardAdded { amount: added, .. },
+                ..
+            }) if added.0 == amount
+        )));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::Nep141(Nep141Event {
+                event_kind: Nep141EventKind::FtMint(_),
+                ..
+            })
+        )));
+
+        assert!(view::get_deposits(&contract).await?.is_empty());
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount))]
+        );
+        assert_eq!(
+            view::ft_total_supply(&contract).await?.0,
+            total_supply + shares
+        );
+        let [snapshot] = &view::get_mint_snapshots(&contract, None, None).await?[..] else {
+            panic!("expected a single mint snapshot");
+        };
+        assert_eq!(snapshot.shares_minted.0, shares);
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_mint_with_mode() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let token_contract = &token_contracts[0];
+        let one_share = 10u128.pow(24);
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+        call::mint_tokens(token_contract, dao_contract.id(), 1_500_000).await?;
+
+        // minting stays restricted to the owner
+        let (proposal_id, _) = call::propose_grant_role(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            council.id(),
+            "Operator",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        for (amount, mode, expected_shares, value_before, dilution_bps) in [
+            (
+                1_000_000,
+                json!({
+                    "mode": "target_price",
+                    "reference_token": token_contract.id(),
+                    "price": U128(1_000),
+                }),
+                1_000 * one_share,
+                0,
+                10_000,
+            ),
+            // keeps the price of 1k tokens per share
+            (
+                500_000,
+                json!({
+                    "mode": "preserve_value",
+                    "reference_token": token_contract.id(),
+                }),
+                500 * one_share,
+                1_000,
+                3_333,
+            ),
+        ] {
+            let (proposal_id, _) = call::propose_deposit_tokens(
+                &council,
+                dao_contract.id(),
+                token_contract.id(),
+                contract.id(),
+                amount,
+            )
+            .await?;
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+
+            let simulation = view::simulate_mint(&contract, expected_shares).await?;
+            assert_eq!(simulation.dilution_bps, dilution_bps);
+            let [share_value] = &simulation.share_values[..] else {
+                panic!("expected a single share value");
+            };
+            assert_eq!(&share_value.token_id, token_contract.id());
+            assert_eq!(share_value.before.0, value_before);
+            assert_eq!(share_value.after.0, 1_000);
+
+            assert!(call::mint_with_mode(&council, contract.id(), mode.clone())
+                .await
+                .is_err());
+
+            let (proposal_id, _) =
+                call::propose_mint_with_mode(&council, dao_contract.id(), contract.id(), mode)
+                    .await?;
+            let (_, events) = call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+            assert!(events.iter().any(|event| matches!(
+                event,
+                ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                    event_kind: ValidatorRewardsEventKind::MintPriced { shares, share_values },
+                    ..
+                }) if shares.0 == expected_shares
+                    && share_values == &vec![(token_contract.id().clone(), U128(1_000))]
+            )));
+        }
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 1_500 * one_share);
+
+        // nothing is left to mint
+        let (proposal_id, _) = call::propose_mint_with_mode(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            json!({
+                "mode": "preserve_value",
+                "reference_token": token_contract.id(),
+            }),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 1_500 * one_share);
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_owner_payout_rollback() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            near,
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let receiver = near
+            .create_subaccount("receiver")
+            .initial_balance(NearToken::from_near(10))
+            .transact()
+            .await?
+            .into_result()?;
+        let token_contract = &token_contracts[0];
+        let amount = 1_000_000;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+        call::mint_tokens(token_contract, dao_contract.id(), amount).await?;
+        let (proposal_id, _) = call::propose_deposit_and_mint(
+            &council,
+            dao_contract.id(),
+            token_contract.id(),
+            contract.id(),
+            amount,
+            1_000,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // the receiver isn't registered with the token, so the rewards are restored
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            1_000,
+            Some(receiver.id()),
+        )
+        .await?;
+        let (_, events) = call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::OwnerPayoutFailed {
+                    source: PayoutSource::Reward,
+                    receiver_id,
+                    ..
+                },
+                ..
+            }) if receiver_id == receiver.id()
+        )));
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount))]
+        );
+
+        call::storage_deposit(token_contract, &council, Some(receiver.id()), None).await?;
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            1_000,
+            Some(receiver.id()),
+        )
+        .await?;
+        let (_, events) = call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::RewardWithdrawn {
+                    receiver_id,
+                    amount: U128(1_000),
+                    ..
+                },
+                ..
+            }) if receiver_id == receiver.id()
+        )));
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount - 1_000))]
+        );
+        assert_eq!(
+            view::ft_balance_of(token_contract, receiver.id()).await?.0,
+            1_000
+        );
+
+        // an operator can only withdraw deposits to the owner
+        call::mint_tokens(token_contract, dao_contract.id(), 1_000).await?;
+        for (proposal_id, _) in [
+            call::propose_grant_role(
+                &council,
+                dao_contract.id(),
+                contract.id(),
+                council.id(),
+                "Operator",
+            )
+            .await?,
+            call::propose_deposit_tokens(
+                &council,
+                dao_contract.id(),
+                token_contract.id(),
+                contract.id(),
+                1_000,
+            )
+            .await?,
+        ] {
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+        }
+        let err = call::withdraw(
+            &council,
+            contract.id(),
+            token_contract.id(),
+            1_000,
+            Some(receiver.id()),
+        )
+        .await
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Only owner can withdraw to another account"));
+
+        let (_, events) =
+            call::withdraw(&council, contract.id(), token_contract.id(), 1_000, None).await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::DepositWithdrawn {
+                    receiver_id,
+                    amount: U128(1_000),
+                    ..
+                },
+                ..
+            }) if receiver_id == dao_contract.id()
+        )));
+        assert!(view::get_deposits(&contract).await?.is_empty());
+        assert_eq!(
+            view::ft_balance_of(token_contract, dao_contract.id())
+                .await?
+                .0,
+            1_000
+        );
+        assert_eq!(
+            view::ft_balance_of(token_contract, receiver.id()).await?.0,
+            1_000
+        );
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_holder_protection() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            worker,
+            near,
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let token_contract = &token_contracts[0];
+        let amount = 1_000_000;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::storage_deposit(token_contract, &council, Some(dao_contract.id()), None).await?;
+        call::storage_deposit(&contract, &council, None, None).await?;
+        call::mint_tokens(token_contract, dao_contract.id(), amount).await?;
+        let receiver = near
+            .create_subaccount("receiver")
+            .initial_balance(NearToken::from_near(10))
+            .transact()
+            .await?
+            .into_result()?;
+
+        // mint 1k shares and move half of them to another holder
+        for (proposal_id, _) in [
+            call::propose_deposit_and_mint(
+                &council,
+                dao_contract.id(),
+                token_contract.id(),
+                contract.id(),
+                amount,
+                1_000,
+            )
+            .await?,
+            call::propose_set_transfer_policy(&council, dao_contract.id(), contract.id(), "Open")
+                .await?,
+            call::propose_ft_transfer(
+                &council,
+                dao_contract.id(),
+                contract.id(),
+                council.id(),
+                500,
+            )
+            .await?,
+            call::propose_set_withdrawal_delay(
+                &council,
+                dao_contract.id(),
+                contract.id(),
+                60 * 1_000_000_000,
+            )
+            .await?,
+            call::propose_set_holder_protection(&council, dao_contract.id(), contract.id(), true)
+                .await?,
+        ] {
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+        }
+        assert_eq!(view::ft_balance_of(&contract, council.id()).await?.0, 500);
+        assert_eq!(
+            view::get_reward_withdrawal_cap(&contract, token_contract.id())
+                .await?
+                .0,
+            amount / 2
+        );
+
+        // withdrawals above the owner's entitlement are rejected
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            amount / 2 + 1,
+            None,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount))]
+        );
+
+        // the owner can't raise its entitlement by minting shares for a tiny deposit
+        call::mint_tokens(token_contract, dao_contract.id(), 1).await?;
+        for (proposal_id, _) in [
+            call::propose_deposit_tokens(
+                &council,
+                dao_contract.id(),
+                token_contract.id(),
+                contract.id(),
+                1,
+            )
+            .await?,
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), 1_000_000)
+                .await?,
+            call::propose_withdraw_reward(
+                &council,
+                dao_contract.id(),
+                contract.id(),
+                token_contract.id(),
+                amount / 10 * 9,
+                None,
+            )
+            .await?,
+        ] {
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+        }
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 1_000);
+        assert_eq!(
+            view::ft_balance_of(&contract, dao_contract.id()).await?.0,
+            500
+        );
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount))]
+        );
+        assert_eq!(
+            view::get_deposits(&contract).await?,
+            vec![(token_contract.id().clone(), U128(1))]
+        );
+
+        // the receiver isn't registered with the token, so the burnt shares are minted again
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            amount / 10,
+            Some(receiver.id()),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount))]
+        );
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 1_000);
+        assert_eq!(
+            view::ft_balance_of(&contract, dao_contract.id()).await?.0,
+            500
+        );
+
+        // withdrawals within the entitlement burn the owner's shares backing them
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            amount / 10,
+            None,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount - amount / 10))]
+        );
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 900);
+        assert_eq!(
+            view::ft_balance_of(&contract, dao_contract.id()).await?.0,
+            400
+        );
+        assert_eq!(
+            view::simulate_burn(&contract, 500).await?,
+            vec![(token_contract.id().clone(), U128(amount / 2))]
+        );
+
+        // announced withdrawals can only be executed after the withdrawal delay
+        let (proposal_id, _) = call::propose_announce_reward_withdrawal(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            amount / 2,
+        )
+        .await?;
+        let (_, events) = call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::RewardWithdrawalAnnounced { .. },
+                ..
+            })
+        )));
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            amount / 2,
+            None,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount - amount / 10))]
+        );
+
+        let [(_, withdrawal)] = &view::get_reward_withdrawals(&contract).await?[..] else {
+            return Err(anyhow::anyhow!("Expected a single announced withdrawal"));
+        };
+        while worker.view_block().await?.timestamp() < withdrawal.executable_at.0 {
+            worker.fast_forward(5).await?;
+        }
+
+        // a failing payout restores the announced withdrawal
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            amount / 2,
+            Some(receiver.id()),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount - amount / 10))]
+        );
+        assert_eq!(view::get_reward_withdrawals(&contract).await?.len(), 1);
+
+        let (proposal_id, _) = call::propose_withdraw_reward(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+            amount / 2,
+            None,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(
+                token_contract.id().clone(),
+                U128(amount - amount / 10 - amount / 2)
+            )]
+        );
+        assert!(view::get_reward_withdrawals(&contract).await?.is_empty());
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_state_version() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init { contract, .. } = initialize_contracts().await?;
+
+        assert_eq!(
+            view::get_state_version(&contract).await?,
+            contract_lib::STATE_VERSION
+        );
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_upgrade_from_baseline() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            near,
+            council,
+            pool_contract,
+            nft_contract,
+            rewarder_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        // the contract as it is currently deployed, before the state was versioned
+        let contract = near
+            .create_subaccount("baseline")
+            .initial_balance(NearToken::from_near(100_000))
+            .transact()
+            .await?
+            .into_result()?
+            .deploy(&tokio::fs::read("../../res/contract_baseline.wasm").await?)
+            .await?
+            .into_result()?;
+        log_tx_result(
+            "new",
+            contract
+                .call("new")
+                .args_json(json!({
+                    "owner": council.id(),
+                    "validator": pool_contract.id(),
+                    "shitzu_token": token_contracts[0].id(),
+                    "shitzu_nft": nft_contract.id(),
+                    "rewarder": rewarder_contract.id(),
+                    "token_whitelist": token_contracts
+                        .iter()
+                        .map(|contract| contract.id())
+                        .collect::<Vec<_>>(),
+                }))
+                .max_gas()
+                .transact()
+                .await?,
+        )?;
+
+        let token_contract = &token_contracts[0];
+        let amount = 1_000_000;
+        call::storage_deposit(token_contract, &council, None, None).await?;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::mint_tokens(token_contract, council.id(), amount).await?;
+        call::ft_transfer_call(&council, token_contract.id(), contract.id(), amount, "").await?;
+        call::mint(&council, contract.id(), 1_000).await?;
+
+        let code = tokio::fs::read("../../res/contract.wasm").await?;
+        call::upgrade_and_migrate(&council, contract.id(), code).await?;
+
+        assert_eq!(
+            view::get_state_version(&contract).await?,
+            contract_lib::STATE_VERSION
+        );
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 1_000);
+        assert_eq!(view::ft_balance_of(&contract, council.id()).await?.0, 1_000);
+        assert_eq!(
+            view::ft_balance_of(&contract, pool_contract.id()).await?.0,
+            0
+        );
+        assert_eq!(
+            view::get_undistributed_rewards(&contract).await?,
+            vec![(token_contract.id().clone(), U128(amount))]
+        );
+        let mut whitelisted_tokens = view::get_whitelisted_tokens(&contract).await?;
+        whitelisted_tokens.sort();
+        assert_eq!(
+            whitelisted_tokens,
+            token_contracts
+                .iter()
+                .map(|contract| contract.id().clone())
+                .collect::<Vec<_>>()
+        );
+        let token = view::get_token(&contract, token_contract.id())
+            .await?
+            .unwrap();
+        assert_eq!(token.state, TokenState::Active);
+
+        // the migrated state is used by the new code
+        call::burn_amount(&council, contract.id(), 100).await?;
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 900);
+        assert_eq!(
+            view::ft_balance_of(token_contract, council.id()).await?.0,
+            amount / 10
+        );
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_migrate_many_accounts() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            near,
+            council,
+            nft_contract,
+            rewarder_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        // council is also the validator, so that it can distribute shares on the baseline contract
+        let contract = near
+            .create_subaccount("baseline")
+            .initial_balance(NearToken::from_near(100_000))
+            .transact()
+            .await?
+            .into_result()?
+            .deploy(&tokio::fs::read("../../res/contract_baseline.wasm").await?)
+            .await?
+            .into_result()?;
+        log_tx_result(
+            "new",
+            contract
+                .call("new")
+                .args_json(json!({
+                    "owner": council.id(),
+                    "validator": council.id(),
+                    "shitzu_token": token_contracts[0].id(),
+                    "shitzu_nft": nft_contract.id(),
+                    "rewarder": rewarder_contract.id(),
+                    "token_whitelist": token_contracts
+                        .iter()
+                        .map(|contract| contract.id())
+                        .collect::<Vec<_>>(),
+                }))
+                .max_gas()
+                .transact()
+                .await?,
+        )?;
+
+        let token_contract = &token_contracts[0];
+        call::storage_deposit(token_contract, &council, None, None).await?;
+        call::storage_deposit(token_contract, &council, Some(contract.id()), None).await?;
+        call::mint_tokens(token_contract, council.id(), 1_000_000).await?;
+        call::ft_transfer_call(&council, token_contract.id(), contract.id(), 1_000_000, "").await?;
+        call::mint(&council, contract.id(), 1_000).await?;
+
+        // 100 holders in 4 batches, every batch with a higher balance than the previous one
+        let holder_ids: Vec<near_sdk::AccountId> = (0..100)
+            .map(|index| format!("holder-{:03}.near", index).parse())
+            .collect::<Result<_, _>>()?;
+        for (index, receiver_ids) in holder_ids.chunks(25).enumerate() {
+            call::ft_transfer_batch(&council, contract.id(), receiver_ids, index as u128 + 1)
+                .await?;
+        }
+
+        let code = tokio::fs::read("../../res/contract.wasm").await?;
+        call::upgrade_and_migrate(&council, contract.id(), code).await?;
+        assert_eq!(
+            view::get_holder_index(&contract).await?,
+            HolderIndex::Building { last_account: None }
+        );
+        assert!(view::get_holder_count(&contract).await.is_err());
+        assert_eq!(view::ft_balance_of(&contract, council.id()).await?.0, 750);
+
+        let (complete, _) = call::index_holders(&council, contract.id(), 30).await?;
+        assert!(!complete);
+        // balance changes of accounts, that aren't indexed yet, are picked up by later batches
+        call::ft_transfer(&council, contract.id(), &holder_ids[99], 10).await?;
+        let mut batches = 1;
+        loop {
+            let (complete, _) = call::index_holders(&council, contract.id(), 30).await?;
+            batches += 1;
+            if complete {
+                break;
+            }
+        }
+        assert_eq!(batches, 4);
+        assert_eq!(
+            view::get_holder_index(&contract).await?,
+            HolderIndex::Complete
+        );
+        assert!(call::index_holders(&council, contract.id(), 30)
+            .await
+            .is_err());
+
+        assert_eq!(view::get_holder_count(&contract).await?, 101);
+        let top_holders = view::get_top_holders(&contract, None, Some(3)).await?;
+        assert_eq!(
+            top_holders,
+            vec![
+                (council.id().clone(), U128(740)),
+                (holder_ids[99].clone(), U128(14)),
+                (holder_ids[98].clone(), U128(4)),
+            ]
+        );
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_staged_upgrade() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            worker,
+            council,
+            contract,
+            dao_contract,
+            ..
+        } = initialize_contracts().await?;
+
+        let code = vec![0u8; 32];
+        let code_hash = Base58CryptoHash::from([1u8; 32]);
+
+        assert!(view::get_staged_upgrade(&contract).await?.is_none());
+        let (proposal_id, _) =
+            call::propose_stage_upgrade(&council, dao_contract.id(), contract.id(), code_hash)
+                .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        let staged_upgrade = view::get_staged_upgrade(&contract).await?.unwrap();
+        assert_eq!(staged_upgrade.code_hash, code_hash);
+        let block = worker.view_block().await?;
+        assert!(staged_upgrade.executable_at.0 > block.timestamp());
+
+        let (proposal_id, _) = call::propose_grant_role(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            council.id(),
+            "Upgrader",
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // the upgrade delay has not passed yet
+        let err = call::upgrade(&council, contract.id(), code.clone())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Upgrade delay has not passed yet"));
+        assert!(view::get_staged_upgrade(&contract).await?.is_some());
+
+        let (proposal_id, _) =
+            call::propose_cancel_upgrade(&council, dao_contract.id(), contract.id()).await?;
+        let (_, events) = call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::UpgradeCancelled { code_hash: hash },
+                ..
+            }) if hash == &code_hash
+        )));
+        assert!(view::get_staged_upgrade(&contract).await?.is_none());
+        let err = call::upgrade(&council, contract.id(), code.clone())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No upgrade staged"));
+
+        // the upgrade delay can't be lowered below the minimum
+        for delay in [0, contract_lib::MIN_UPGRADE_DELAY] {
+            let (proposal_id, _) =
+                call::propose_set_upgrade_delay(&council, dao_contract.id(), contract.id(), delay)
+                    .await?;
+            call::act_proposal(
+                &council,
+                dao_contract.id(),
+                proposal_id,
+                Action::VoteApprove,
+            )
+            .await?;
+        }
+        assert_eq!(
+            view::get_upgrade_delay(&contract).await?.0,
+            contract_lib::MIN_UPGRADE_DELAY
+        );
+
+        // stage the code, that is currently deployed
+        let code_hash =
+            Base58CryptoHash::from(worker.view_account(contract.id()).await?.code_hash.0);
+        let new_code = tokio::fs::read("../../res/contract.wasm").await?;
+        let (proposal_id, _) =
+            call::propose_stage_upgrade(&council, dao_contract.id(), contract.id(), code_hash)
+                .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        let staged_upgrade = view::get_staged_upgrade(&contract).await?.unwrap();
+        let block = worker.view_block().await?;
+        assert!(
+            staged_upgrade.executable_at.0 <= block.timestamp() + contract_lib::MIN_UPGRADE_DELAY
+        );
+        while worker.view_block().await?.timestamp() < staged_upgrade.executable_at.0 {
+            worker.fast_forward(500).await?;
+        }
+
+        // code that doesn't match the staged hash is rejected
+        let err = call::upgrade(&council, contract.id(), code)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Code doesn't match the staged upgrade"));
+        assert!(view::get_staged_upgrade(&contract).await?.is_some());
+
+        let (_, events) = call::upgrade(&council, contract.id(), new_code).await?;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ContractEvent::ValidatorRewards(ValidatorRewardsEvent {
+                event_kind: ValidatorRewardsEventKind::UpgradeExecuted { code_hash: hash },
+                ..
+            }) if hash == &code_hash
+        )));
+        assert!(view::get_staged_upgrade(&contract).await?.is_none());
+        assert_eq!(
+            view::get_state_version(&contract).await?,
+            contract_lib::STATE_VERSION
+        );
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_policy() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            worker,
+            near,
+            council,
+            contract,
+            dao_contract,
+            pool_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let mint_amount = 1_000_000;
+
+        let (proposal_id, _) = call::propose_add_authorized_farm_token(
+            &council,
+            dao_contract.id(),
+            pool_contract.id(),
+            contract.id(),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        try_join_all(token_contracts.iter().cloned().map(|token_contract| {
+            let council = council.clone();
+            let contract = contract.clone();
+            let dao_contract = dao_contract.clone();
+            tokio::spawn(async move {
+                call::storage_deposit(&token_contract, &council, None, None).await?;
+                call::storage_deposit(&token_contract, &council, Some(contract.id()), None).await?;
+                call::storage_deposit(&token_contract, &council, Some(dao_contract.id()), None)
+                    .await?;
+                call::mint_tokens(&token_contract, dao_contract.id(), mint_amount).await?;
+
+                let (proposal_id, _) = call::propose_deposit_tokens(
+                    &council,
+                    dao_contract.id(),
+                    token_contract.id(),
+                    contract.id(),
+                    mint_amount,
+                )
+                .await?;
+                call::act_proposal(
+                    &council,
+                    dao_contract.id(),
+                    proposal_id,
+                    Action::VoteApprove,
+                )
+                .await?;
+                anyhow::Ok(())
+            })
+        }))
+        .await?;
+
+        let shares = NearToken::from_near(1).as_yoctonear();
+
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), shares).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        let block = worker.view_block().await?;
+        let start_date = block.timestamp() + 1_000_000_000 * 60; // 1min
+        let end_date = block.timestamp() + 1_000_000_000 * 60 * 5; // 5min
+        let (proposal_id, _) = call::propose_create_farm(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            pool_contract.id(),
+            shares,
+            "Dogshit".to_string(),
+            start_date,
+            end_date,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        call::storage_deposit(&contract, &council, None, None).await?;
+        call::deposit_and_stake(&council, pool_contract.id(), NearToken::from_near(10_000)).await?;
+
+        while worker.view_block().await?.timestamp() < start_date {
+            worker.fast_forward(5).await?;
+        }
+        worker.fast_forward(5).await?;
+
+        call::claim(&council, pool_contract.id(), contract.id()).await?;
+        let balance = view::ft_balance_of(&contract, council.id()).await?;
+        assert!(balance.0 > 1);
+
+        call::storage_deposit(&contract, &near, None, None).await?;
+        let err = call::ft_transfer(&council, contract.id(), near.id(), balance.0)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Only validator can distribute tokens"));
+
+        let (proposal_id, _) =
+            call::propose_set_transfer_policy(&council, dao_contract.id(), contract.id(), "Open")
+                .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // holders can't transfer more than they own
+        assert!(
+            call::ft_transfer(&council, contract.id(), near.id(), balance.0 + 1)
+                .await
+                .is_err()
+        );
+
+        let amount = balance.0 / 4;
+        call::ft_transfer(&council, contract.id(), near.id(), amount).await?;
+        assert_eq!(
+            view::ft_balance_of(&contract, council.id()).await?.0,
+            balance.0 - amount
+        );
+        assert_eq!(view::ft_balance_of(&contract, near.id()).await?.0, amount);
+        assert_eq!(view::ft_total_supply(&contract).await?.0, shares);
+
+        let accounts = view::get_accounts(&contract, None, None).await?;
+        assert!(accounts.contains(&(near.id().clone(), U128(amount))));
+        let total: u128 = accounts.iter().map(|(_, balance)| balance.0).sum();
+        assert_eq!(total, shares);
+        let first_page = view::get_accounts(&contract, None, Some(2)).await?;
+        let second_page = view::get_accounts(&contract, Some(&first_page[1].0), None).await?;
+        assert_eq!([first_page, second_page].concat(), accounts);
+
+        let holders = accounts.iter().filter(|(_, balance)| balance.0 > 0).count();
+        assert_eq!(view::get_holder_count(&contract).await? as usize, holders);
+        let top_holders = view::get_top_holders(&contract, None, None).await?;
+        assert_eq!(top_holders.len(), holders);
+        assert!(top_holders
+            .windows(2)
+            .all(|holders| holders[0].1 .0 >= holders[1].1 .0));
+        assert_eq!(
+            top_holders[0].1,
+            accounts.iter().map(|(_, balance)| *balance).max().unwrap()
+        );
+        let first_page = view::get_top_holders(&contract, None, Some(1)).await?;
+        let second_page = view::get_top_holders(&contract, Some(&first_page[0].0), None).await?;
+        assert_eq!([first_page, second_page].concat(), top_holders);
+
+        // holders that transfer their whole balance are no longer counted
+        call::ft_transfer(&council, contract.id(), near.id(), balance.0 - amount).await?;
+        assert_eq!(
+            view::get_holder_count(&contract).await? as usize,
+            holders - 1
+        );
+        assert!(!view::get_top_holders(&contract, None, None)
+            .await?
+            .iter()
+            .any(|(account_id, _)| account_id == council.id()));
+
+        anyhow::Ok(())
+    })
+    .await;
+    chain.kill()?;
+    match thread {
+        Err(err) => Err(anyhow::anyhow!(err)),
+        Ok(Err(err)) => Err(anyhow::anyhow!(err)),
+        Ok(_) => anyhow::Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn test_dust_sweep() -> anyhow::Result<()> {
+    let mut chain = initialize_blockchain().await?;
+
+    let thread = tokio::spawn(async {
+        let Init {
+            council,
+            contract,
+            dao_contract,
+            token_contracts,
+            ..
+        } = initialize_contracts().await?;
+
+        let mint_amount = 1_000_000;
+
+        try_join_all(token_contracts.iter().cloned().map(|token_contract| {
+            let council = council.clone();
+            let contract = contract.clone();
+            let dao_contract = dao_contract.clone();
+            tokio::spawn(async move {
+                call::storage_deposit(&token_contract, &council, Some(contract.id()), None).await?;
+                call::storage_deposit(&token_contract, &council, Some(dao_contract.id()), None)
+                    .await?;
+                call::mint_tokens(&token_contract, dao_contract.id(), mint_amount * 2).await?;
+
+                let (proposal_id, _) = call::propose_deposit_tokens(
+                    &council,
+                    dao_contract.id(),
+                    token_contract.id(),
+                    contract.id(),
+                    mint_amount,
+                )
+                .await?;
+                call::act_proposal(
+                    &council,
+                    dao_contract.id(),
+                    proposal_id,
+                    Action::VoteApprove,
+                )
+                .await?;
+                anyhow::Ok(())
+            })
+        }))
+        .await?;
+
+        let shares = NearToken::from_near(1).as_yoctonear();
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), shares).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+
+        // the owner burns the whole supply and receives all wrapped tokens
+        let (proposal_id, _) =
+            call::propose_burn(&council, dao_contract.id(), contract.id()).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert_eq!(view::ft_total_supply(&contract).await?.0, 0);
+        assert!(view::get_undistributed_rewards(&contract).await?.is_empty());
+        assert!(view::get_dust(&contract).await?.is_empty());
+        assert!(view::simulate_burn(&contract, shares).await?.is_empty());
+        for token_contract in &token_contracts {
+            let balance = view::ft_balance_of(token_contract, dao_contract.id()).await?;
+            assert_eq!(balance.0, mint_amount * 2);
+        }
+
+        // rewards added without shares can't be redeemed and are swept by the owner
+        let token_contract = &token_contracts[0];
+        let (proposal_id, _) = call::propose_deposit_tokens(
+            &council,
+            dao_contract.id(),
+            token_contract.id(),
+            contract.id(),
+            mint_amount,
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        let (proposal_id, _) =
+            call::propose_mint_shares(&council, dao_contract.id(), contract.id(), 0).await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        for (_, amount) in view::simulate_burn(&contract, shares).await? {
+            assert_eq!(amount.0, 0);
+        }
+
+        let (proposal_id, _) = call::propose_sweep_dust(
+            &council,
+            dao_contract.id(),
+            contract.id(),
+            token_contract.id(),
+        )
+        .await?;
+        call::act_proposal(
+            &council,
+            dao_contract.id(),
+            proposal_id,
+            Action::VoteApprove,
+        )
+        .await?;
+        assert!(view::get_undistributed_rewards(&contract).await?.is_empty());
+        assert!(view::get_dust(&contract).await?.is_empty());
+        let balance = view::ft_balance_of(token_contract, dao_contract.id()).await?;
+        assert_eq!(balance.0, mint_amount * 2);
+
         anyhow::Ok(())
     })
     .await;
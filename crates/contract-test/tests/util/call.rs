@@ -5,11 +5,12 @@ use crate::{
 };
 use near_contract_standards::non_fungible_token::TokenId;
 use near_sdk::{
-    json_types::{Base64VecU8, U128},
+    json_types::{Base58CryptoHash, Base64VecU8, U128, U64},
     serde_json::{self, json},
     Gas,
 };
 use near_workspaces::{
+    operations::Function,
     result::{ExecutionResult, Value},
     types::NearToken,
     Account, AccountId, Contract,
@@ -34,6 +35,24 @@ pub async fn storage_deposit(
     Ok(res)
 }
 
+pub async fn storage_unregister(
+    contract: &Contract,
+    sender: &Account,
+    force: Option<bool>,
+) -> anyhow::Result<(bool, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        &format!("{} storage_unregister", contract.id()),
+        sender
+            .call(contract.id(), "storage_unregister")
+            .args_json((force,))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok((res.json()?, events))
+}
+
 pub async fn mint_tokens(
     token: &Contract,
     receiver: &AccountId,
@@ -121,6 +140,48 @@ pub async fn propose_deposit_tokens(
     .await
 }
 
+pub async fn propose_deposit_and_mint(
+    sender: &Account,
+    dao: &AccountId,
+    token_id: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+    shares: u128,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_deposit_and_mint",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: token_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "ft_transfer_call".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "receiver_id": receiver_id,
+                            "amount": U128(amount),
+                            "msg": json!({
+                                "action": "deposit_and_mint",
+                                "shares": U128(shares)
+                            })
+                            .to_string()
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(1),
+                    gas: Gas::from_tgas(100),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
 pub async fn propose_mint_shares(
     sender: &Account,
     dao: &AccountId,
@@ -155,44 +216,32 @@ pub async fn propose_mint_shares(
     .await
 }
 
-#[allow(clippy::too_many_arguments)]
-pub async fn propose_create_farm(
+pub async fn propose_mint_with_mode(
     sender: &Account,
     dao: &AccountId,
-    token_id: &AccountId,
-    receiver_id: &AccountId,
-    amount: u128,
-    name: String,
-    start_date: u64,
-    end_date: u64,
+    contract_id: &AccountId,
+    mode: serde_json::Value,
 ) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
     add_proposal(
-        "propose_create_farm",
+        "propose_mint_with_mode",
         sender,
         dao,
         ProposalInput {
             description: "".to_string(),
             kind: ProposalKind::FunctionCall {
-                receiver_id: token_id.clone(),
+                receiver_id: contract_id.clone(),
                 actions: vec![ActionCall {
-                    method_name: "ft_transfer_call".to_string(),
+                    method_name: "mint_with_mode".to_string(),
                     args: Base64VecU8::from(
                         json!({
-                            "receiver_id": receiver_id,
-                            "amount": U128(amount),
-                            "msg": serde_json::to_string(&FarmingDetails {
-                                name: Some(name),
-                                start_date: Some(start_date.into()),
-                                end_date: end_date.into(),
-                                farm_id: None
-                            })?
+                            "mode": mode
                         })
                         .to_string()
                         .as_bytes()
                         .to_vec(),
                     ),
-                    deposit: NearToken::from_yoctonear(1),
-                    gas: Gas::from_tgas(80),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
                 }],
             },
         },
@@ -201,44 +250,34 @@ pub async fn propose_create_farm(
     .await
 }
 
-#[allow(clippy::too_many_arguments)]
-pub async fn propose_update_farm(
+pub async fn propose_set_penalty(
     sender: &Account,
     dao: &AccountId,
-    token_id: &AccountId,
-    receiver_id: &AccountId,
-    amount: u128,
-    name: String,
-    end_date: u64,
-    farm_id: u64,
+    contract_id: &AccountId,
+    rate_bps: u16,
+    destination: &str,
 ) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
     add_proposal(
-        "propose_update_farm",
+        "propose_set_penalty",
         sender,
         dao,
         ProposalInput {
             description: "".to_string(),
             kind: ProposalKind::FunctionCall {
-                receiver_id: token_id.clone(),
+                receiver_id: contract_id.clone(),
                 actions: vec![ActionCall {
-                    method_name: "ft_transfer_call".to_string(),
+                    method_name: "set_penalty".to_string(),
                     args: Base64VecU8::from(
                         json!({
-                            "receiver_id": receiver_id,
-                            "amount": U128(amount),
-                            "msg": serde_json::to_string(&FarmingDetails {
-                                name: Some(name),
-                                start_date: None,
-                                end_date: end_date.into(),
-                                farm_id: Some(farm_id)
-                            })?
+                            "rate_bps": rate_bps,
+                            "destination": destination
                         })
                         .to_string()
                         .as_bytes()
                         .to_vec(),
                     ),
-                    deposit: NearToken::from_yoctonear(1),
-                    gas: Gas::from_tgas(80),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
                 }],
             },
         },
@@ -247,13 +286,15 @@ pub async fn propose_update_farm(
     .await
 }
 
-pub async fn propose_burn(
+pub async fn propose_set_score_multiplier(
     sender: &Account,
     dao: &AccountId,
     contract_id: &AccountId,
+    token_id: &AccountId,
+    multiplier_bps: Option<u32>,
 ) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
     add_proposal(
-        "propose_burn",
+        "propose_set_score_multiplier",
         sender,
         dao,
         ProposalInput {
@@ -261,10 +302,18 @@ pub async fn propose_burn(
             kind: ProposalKind::FunctionCall {
                 receiver_id: contract_id.clone(),
                 actions: vec![ActionCall {
-                    method_name: "burn".to_string(),
-                    args: Base64VecU8::from(vec![]),
-                    deposit: NearToken::from_yoctonear(1),
-                    gas: Gas::from_tgas(150),
+                    method_name: "set_score_multiplier".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "token_id": token_id,
+                            "multiplier_bps": multiplier_bps
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
                 }],
             },
         },
@@ -273,15 +322,14 @@ pub async fn propose_burn(
     .await
 }
 
-pub async fn propose_withdraw_reward(
+pub async fn propose_set_validator(
     sender: &Account,
     dao: &AccountId,
     contract_id: &AccountId,
-    token_id: &AccountId,
-    amount: u128,
+    validator_id: &AccountId,
 ) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
     add_proposal(
-        "propose_withdraw_reward",
+        "propose_set_validator",
         sender,
         dao,
         ProposalInput {
@@ -289,11 +337,10 @@ pub async fn propose_withdraw_reward(
             kind: ProposalKind::FunctionCall {
                 receiver_id: contract_id.clone(),
                 actions: vec![ActionCall {
-                    method_name: "withdraw_reward".to_string(),
+                    method_name: "set_validator".to_string(),
                     args: Base64VecU8::from(
                         json!({
-                            "token_id": token_id,
-                            "amount": U128(amount)
+                            "validator_id": validator_id
                         })
                         .to_string()
                         .as_bytes()
@@ -309,14 +356,15 @@ pub async fn propose_withdraw_reward(
     .await
 }
 
-pub async fn propose_remove_reward(
+pub async fn propose_grant_role(
     sender: &Account,
     dao: &AccountId,
     contract_id: &AccountId,
-    token_id: &AccountId,
+    account_id: &AccountId,
+    role: &str,
 ) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
     add_proposal(
-        "propose_remove_reward",
+        "propose_grant_role",
         sender,
         dao,
         ProposalInput {
@@ -324,10 +372,11 @@ pub async fn propose_remove_reward(
             kind: ProposalKind::FunctionCall {
                 receiver_id: contract_id.clone(),
                 actions: vec![ActionCall {
-                    method_name: "remove_reward".to_string(),
+                    method_name: "grant_role".to_string(),
                     args: Base64VecU8::from(
                         json!({
-                            "token_id": token_id,
+                            "account_id": account_id,
+                            "role": role
                         })
                         .to_string()
                         .as_bytes()
@@ -343,79 +392,641 @@ pub async fn propose_remove_reward(
     .await
 }
 
-pub async fn new_dao(
-    contract: &Contract,
-    config: DaoConfig,
-    policy: DaoPolicy,
-) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
-    log_tx_result(
-        "DAO: new",
-        contract
-            .call("new")
-            .args_json((config, policy))
-            .max_gas()
-            .transact()
-            .await?,
-    )
-}
-
-async fn add_proposal(
-    ident: &str,
+pub async fn propose_revoke_role(
     sender: &Account,
     dao: &AccountId,
-    proposal: ProposalInput,
-    deposit: NearToken,
+    contract_id: &AccountId,
+    account_id: &AccountId,
+    role: &str,
 ) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
-    let (res, events) = log_tx_result(
-        ident,
-        sender
-            .call(dao, "add_proposal")
-            .args_json((proposal,))
-            .max_gas()
-            .deposit(deposit)
-            .transact()
-            .await?,
-    )?;
-    Ok((res.json()?, events))
+    add_proposal(
+        "propose_revoke_role",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "revoke_role".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "account_id": account_id,
+                            "role": role
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
 }
 
-pub async fn act_proposal(
+pub async fn propose_owner(
     sender: &Account,
     dao: &AccountId,
-    proposal_id: u64,
-    action: Action,
-) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
-    log_tx_result(
-        "DAO: act_proposal",
-        sender
-            .call(dao, "act_proposal")
-            .args_json((proposal_id, action, None::<String>))
-            .max_gas()
-            .transact()
-            .await?,
+    contract_id: &AccountId,
+    new_owner_id: &AccountId,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_owner",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "propose_owner".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "new_owner_id": new_owner_id
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
     )
+    .await
 }
 
-pub async fn deposit_and_stake(
+pub async fn propose_stage_upgrade(
     sender: &Account,
-    pool: &AccountId,
-    deposit: NearToken,
-) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
-    log_tx_result(
-        "deposit_and_stake",
-        sender
-            .call(pool, "deposit_and_stake")
-            .deposit(deposit)
-            .max_gas()
-            .transact()
-            .await?,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    code_hash: Base58CryptoHash,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_stage_upgrade",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "stage_upgrade".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "code_hash": code_hash
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
     )
+    .await
 }
 
-pub async fn claim(
+pub async fn propose_set_withdrawal_delay(
     sender: &Account,
-    pool: &AccountId,
-    token_id: &AccountId,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    delay: u64,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_set_withdrawal_delay",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "set_withdrawal_delay".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "delay": U64(delay)
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_cancel_upgrade(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_cancel_upgrade",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "cancel_upgrade".to_string(),
+                    args: Base64VecU8::from(json!({}).to_string().as_bytes().to_vec()),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_set_upgrade_delay(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    delay: u64,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_set_upgrade_delay",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "set_upgrade_delay".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "delay": U64(delay)
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_set_transfer_policy(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    policy: &str,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_set_transfer_policy",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "set_transfer_policy".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "policy": policy
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_ft_transfer(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_ft_transfer",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "ft_transfer".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "receiver_id": receiver_id,
+                            "amount": U128(amount)
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(1),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_set_holder_protection(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    enabled: bool,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_set_holder_protection",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "set_holder_protection".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "enabled": enabled
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_announce_reward_withdrawal(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    token_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_announce_reward_withdrawal",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "announce_reward_withdrawal".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "token_id": token_id,
+                            "amount": U128(amount)
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn propose_create_farm(
+    sender: &Account,
+    dao: &AccountId,
+    token_id: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+    name: String,
+    start_date: u64,
+    end_date: u64,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_create_farm",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: token_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "ft_transfer_call".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "receiver_id": receiver_id,
+                            "amount": U128(amount),
+                            "msg": serde_json::to_string(&FarmingDetails {
+                                name: Some(name),
+                                start_date: Some(start_date.into()),
+                                end_date: end_date.into(),
+                                farm_id: None
+                            })?
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(1),
+                    gas: Gas::from_tgas(80),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn propose_update_farm(
+    sender: &Account,
+    dao: &AccountId,
+    token_id: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+    name: String,
+    end_date: u64,
+    farm_id: u64,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_update_farm",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: token_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "ft_transfer_call".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "receiver_id": receiver_id,
+                            "amount": U128(amount),
+                            "msg": serde_json::to_string(&FarmingDetails {
+                                name: Some(name),
+                                start_date: None,
+                                end_date: end_date.into(),
+                                farm_id: Some(farm_id)
+                            })?
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(1),
+                    gas: Gas::from_tgas(80),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_burn(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_burn",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "burn".to_string(),
+                    args: Base64VecU8::from(vec![]),
+                    deposit: NearToken::from_yoctonear(1),
+                    gas: Gas::from_tgas(150),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_sweep_dust(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    token_id: &AccountId,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_sweep_dust",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "sweep_dust".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "token_id": token_id
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(100),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_withdraw_reward(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    token_id: &AccountId,
+    amount: u128,
+    receiver_id: Option<&AccountId>,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_withdraw_reward",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "withdraw_reward".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "token_id": token_id,
+                            "amount": U128(amount),
+                            "receiver_id": receiver_id
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn propose_remove_reward(
+    sender: &Account,
+    dao: &AccountId,
+    contract_id: &AccountId,
+    token_id: &AccountId,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    add_proposal(
+        "propose_remove_reward",
+        sender,
+        dao,
+        ProposalInput {
+            description: "".to_string(),
+            kind: ProposalKind::FunctionCall {
+                receiver_id: contract_id.clone(),
+                actions: vec![ActionCall {
+                    method_name: "remove_reward".to_string(),
+                    args: Base64VecU8::from(
+                        json!({
+                            "token_id": token_id,
+                        })
+                        .to_string()
+                        .as_bytes()
+                        .to_vec(),
+                    ),
+                    deposit: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(50),
+                }],
+            },
+        },
+        NearToken::from_near(1),
+    )
+    .await
+}
+
+pub async fn new_dao(
+    contract: &Contract,
+    config: DaoConfig,
+    policy: DaoPolicy,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "DAO: new",
+        contract
+            .call("new")
+            .args_json((config, policy))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+async fn add_proposal(
+    ident: &str,
+    sender: &Account,
+    dao: &AccountId,
+    proposal: ProposalInput,
+    deposit: NearToken,
+) -> anyhow::Result<(u64, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        ident,
+        sender
+            .call(dao, "add_proposal")
+            .args_json((proposal,))
+            .max_gas()
+            .deposit(deposit)
+            .transact()
+            .await?,
+    )?;
+    Ok((res.json()?, events))
+}
+
+pub async fn act_proposal(
+    sender: &Account,
+    dao: &AccountId,
+    proposal_id: u64,
+    action: Action,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "DAO: act_proposal",
+        sender
+            .call(dao, "act_proposal")
+            .args_json((proposal_id, action, None::<String>))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn deposit_and_stake(
+    sender: &Account,
+    pool: &AccountId,
+    deposit: NearToken,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "deposit_and_stake",
+        sender
+            .call(pool, "deposit_and_stake")
+            .deposit(deposit)
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn claim(
+    sender: &Account,
+    pool: &AccountId,
+    token_id: &AccountId,
 ) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
     log_tx_result(
         "claim",
@@ -429,6 +1040,43 @@ pub async fn claim(
     )
 }
 
+pub async fn ft_transfer(
+    sender: &Account,
+    contract: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "ft_transfer",
+        sender
+            .call(contract, "ft_transfer")
+            .args_json((receiver_id, U128(amount), None::<String>))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+/// Transfers `amount` to every receiver in a single transaction.
+pub async fn ft_transfer_batch(
+    sender: &Account,
+    contract: &AccountId,
+    receiver_ids: &[AccountId],
+    amount: u128,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    let mut tx = sender.batch(contract);
+    for receiver_id in receiver_ids {
+        tx = tx.call(
+            Function::new("ft_transfer")
+                .args_json((receiver_id, U128(amount), None::<String>))
+                .deposit(NearToken::from_yoctonear(1))
+                .gas(Gas::from_tgas(10)),
+        );
+    }
+    log_tx_result("ft_transfer batch", tx.transact().await?)
+}
+
 pub async fn burn(
     sender: &Account,
     contract: &AccountId,
@@ -445,6 +1093,327 @@ pub async fn burn(
     Ok((res.json()?, events))
 }
 
+pub async fn burn_amount(
+    sender: &Account,
+    contract: &AccountId,
+    amount: u128,
+) -> anyhow::Result<(U128, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "burn_amount",
+        sender
+            .call(contract, "burn_amount")
+            .args_json((U128(amount),))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok((res.json()?, events))
+}
+
+pub async fn burn_to(
+    sender: &Account,
+    contract: &AccountId,
+    receiver_id: &AccountId,
+    amount: Option<u128>,
+) -> anyhow::Result<(U128, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "burn_to",
+        sender
+            .call(contract, "burn_to")
+            .args_json((receiver_id, amount.map(U128)))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok((res.json()?, events))
+}
+
+pub async fn burn_tokens(
+    sender: &Account,
+    contract: &AccountId,
+    token_ids: &[AccountId],
+    amount: Option<u128>,
+    receiver_id: Option<&AccountId>,
+) -> anyhow::Result<(U128, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "burn_tokens",
+        sender
+            .call(contract, "burn_tokens")
+            .args_json((token_ids, amount.map(U128), receiver_id))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok((res.json()?, events))
+}
+
+pub async fn claim_pending(
+    sender: &Account,
+    contract: &AccountId,
+    token_id: &AccountId,
+) -> anyhow::Result<(bool, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "claim_pending",
+        sender
+            .call(contract, "claim_pending")
+            .args_json((token_id,))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok((res.json()?, events))
+}
+
+pub async fn whitelist_add_token(
+    sender: &Account,
+    contract: &AccountId,
+    token_id: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "whitelist_add_token",
+        sender
+            .call(contract, "whitelist_add_token")
+            .args_json((token_id,))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn whitelist_remove_token(
+    sender: &Account,
+    contract: &AccountId,
+    token_id: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "whitelist_remove_token",
+        sender
+            .call(contract, "whitelist_remove_token")
+            .args_json((token_id,))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn set_token_state(
+    sender: &Account,
+    contract: &AccountId,
+    token_id: &AccountId,
+    state: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "set_token_state",
+        sender
+            .call(contract, "set_token_state")
+            .args_json((token_id, state))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn add_sponsor(
+    sender: &Account,
+    contract: &AccountId,
+    account_id: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "add_sponsor",
+        sender
+            .call(contract, "add_sponsor")
+            .args_json((account_id,))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn refund_sponsor_deposit(
+    sender: &Account,
+    contract: &AccountId,
+    sponsor_id: &AccountId,
+    token_id: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "refund_sponsor_deposit",
+        sender
+            .call(contract, "refund_sponsor_deposit")
+            .args_json((sponsor_id, token_id))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_transfer_call(
+    sender: &Account,
+    token: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+    msg: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        &format!("{} ft_transfer_call", token),
+        sender
+            .call(token, "ft_transfer_call")
+            .args_json((receiver_id, U128(amount), None::<String>, msg))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn mint_with_mode(
+    sender: &Account,
+    contract: &AccountId,
+    mode: serde_json::Value,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "mint_with_mode",
+        sender
+            .call(contract, "mint_with_mode")
+            .args_json((mode,))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn accept_owner(
+    sender: &Account,
+    contract: &AccountId,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "accept_owner",
+        sender
+            .call(contract, "accept_owner")
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn upgrade(
+    sender: &Account,
+    contract: &AccountId,
+    code: Vec<u8>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "upgrade",
+        sender
+            .call(contract, "upgrade")
+            .args(code)
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn upgrade_and_migrate(
+    sender: &Account,
+    contract: &AccountId,
+    code: Vec<u8>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "upgrade_and_migrate",
+        sender
+            .call(contract, "upgrade_and_migrate")
+            .args(code)
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn withdraw(
+    sender: &Account,
+    contract: &AccountId,
+    token_id: &AccountId,
+    amount: u128,
+    receiver_id: Option<&AccountId>,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "withdraw",
+        sender
+            .call(contract, "withdraw")
+            .args_json((token_id, U128(amount), receiver_id))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn index_holders(
+    sender: &Account,
+    contract: &AccountId,
+    limit: u32,
+) -> anyhow::Result<(bool, Vec<ContractEvent>)> {
+    let (res, events) = log_tx_result(
+        "index_holders",
+        sender
+            .call(contract, "index_holders")
+            .args_json((limit,))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok((res.json()?, events))
+}
+
+pub async fn mint(
+    sender: &Account,
+    contract: &AccountId,
+    shares: u128,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "mint",
+        sender
+            .call(contract, "mint")
+            .args_json((U128(shares),))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn pause(
+    sender: &Account,
+    contract: &AccountId,
+    feature: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "pause",
+        sender
+            .call(contract, "pause")
+            .args_json((feature,))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn unpause(
+    sender: &Account,
+    contract: &AccountId,
+    feature: &str,
+) -> anyhow::Result<(ExecutionResult<Value>, Vec<ContractEvent>)> {
+    log_tx_result(
+        "unpause",
+        sender
+            .call(contract, "unpause")
+            .args_json((feature,))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
 pub async fn nft_mint(
     sender: &Account,
     nft: &AccountId,
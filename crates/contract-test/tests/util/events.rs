@@ -1,6 +1,11 @@
+use contract_lib::{
+    HolderProtection, PausableFeature, PayoutSource, PenaltyDestination, Role, TokenState,
+    TransferPolicy,
+};
 use near_sdk::{
+    json_types::{Base58CryptoHash, U128, U64},
     serde::{Deserialize, Serialize},
-    AccountId,
+    serde_json, AccountId,
 };
 use owo_colors::OwoColorize;
 use std::fmt::{self, Display, Formatter};
@@ -11,6 +16,7 @@ use std::fmt::{self, Display, Formatter};
 #[serde(rename_all = "kebab-case")]
 pub enum ContractEvent {
     Nep141(Nep141Event),
+    ValidatorRewards(ValidatorRewardsEvent),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -32,6 +38,178 @@ pub enum Nep141EventKind {
     FtBurn(Vec<FtBurn>),
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorRewardsEvent {
+    pub version: String,
+    #[serde(flatten)]
+    pub event_kind: ValidatorRewardsEventKind,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorRewardsEventKind {
+    TokenWhitelisted {
+        token_id: AccountId,
+    },
+    TokenUnwhitelisted {
+        token_id: AccountId,
+    },
+    TokenStateUpdated {
+        token_id: AccountId,
+        state: TokenState,
+    },
+    TokenMetadataUpdated {
+        token_id: AccountId,
+        symbol: String,
+        decimals: u8,
+    },
+    TokenDeposited {
+        token_id: AccountId,
+        sender_id: AccountId,
+        amount: U128,
+    },
+    SponsorAdded {
+        account_id: AccountId,
+    },
+    SponsorRemoved {
+        account_id: AccountId,
+    },
+    SponsorDepositRefunded {
+        sponsor_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    },
+    DepositWithdrawn {
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    RewardAdded {
+        token_id: AccountId,
+        amount: U128,
+    },
+    MintPriced {
+        shares: U128,
+        share_values: Vec<(AccountId, U128)>,
+    },
+    RewardWithdrawn {
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    HolderProtectionUpdated {
+        protection: HolderProtection,
+    },
+    WithdrawalDelayUpdated {
+        delay: U64,
+    },
+    RewardWithdrawalAnnounced {
+        token_id: AccountId,
+        amount: U128,
+        executable_at: U64,
+    },
+    RewardWithdrawalCancelled {
+        token_id: AccountId,
+        amount: U128,
+    },
+    RewardRemoved {
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    OwnerPayoutFailed {
+        source: PayoutSource,
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    BurnPayout {
+        account_id: AccountId,
+        receiver_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    },
+    PenaltyApplied {
+        account_id: AccountId,
+        amount: U128,
+        destination: PenaltyDestination,
+    },
+    PenaltyUpdated {
+        rate_bps: u16,
+        destination: PenaltyDestination,
+    },
+    ScoreMultiplierUpdated {
+        token_id: AccountId,
+        multiplier_bps: Option<u32>,
+    },
+    RoleGranted {
+        account_id: AccountId,
+        role: Role,
+    },
+    RoleRevoked {
+        account_id: AccountId,
+        role: Role,
+    },
+    Paused {
+        feature: PausableFeature,
+    },
+    Unpaused {
+        feature: PausableFeature,
+    },
+    OwnerProposed {
+        new_owner_id: AccountId,
+    },
+    OwnerTransferred {
+        old_owner_id: AccountId,
+        new_owner_id: AccountId,
+    },
+    ValidatorUpdated {
+        old_validator_id: AccountId,
+        new_validator_id: AccountId,
+    },
+    RewarderUpdated {
+        old_rewarder_id: AccountId,
+        new_rewarder_id: AccountId,
+    },
+    ShitzuNftUpdated {
+        old_nft_id: AccountId,
+        new_nft_id: AccountId,
+    },
+    UpgradeStaged {
+        code_hash: Base58CryptoHash,
+        executable_at: U64,
+    },
+    UpgradeCancelled {
+        code_hash: Base58CryptoHash,
+    },
+    UpgradeExecuted {
+        code_hash: Base58CryptoHash,
+    },
+    UpgradeDelayUpdated {
+        delay: U64,
+    },
+    TransferPolicyUpdated {
+        policy: TransferPolicy,
+    },
+    TransferAllowlistAdded {
+        account_id: AccountId,
+    },
+    TransferAllowlistRemoved {
+        account_id: AccountId,
+    },
+    DustCollected {
+        token_id: AccountId,
+        amount: U128,
+    },
+    DustSwept {
+        token_id: AccountId,
+        amount: U128,
+    },
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct FtTransfer {
@@ -61,6 +239,9 @@ impl Display for ContractEvent {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             ContractEvent::Nep141(event) => formatter.write_fmt(format_args!("{}", event)),
+            ContractEvent::ValidatorRewards(event) => {
+                formatter.write_fmt(format_args!("{}", event))
+            }
         }
     }
 }
@@ -105,6 +286,32 @@ impl Display for Nep141Event {
     }
 }
 
+impl Display for ValidatorRewardsEvent {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let event_kind = serde_json::to_value(&self.event_kind).map_err(|_| fmt::Error)?;
+        formatter.write_fmt(format_args!(
+            "{}: {}",
+            "event".bright_cyan(),
+            event_kind["event"].as_str().unwrap_or_default()
+        ))?;
+        formatter.write_fmt(format_args!(
+            "\n{}: validator-rewards",
+            "standard".bright_cyan(),
+        ))?;
+        formatter.write_fmt(format_args!(
+            "\n{}: {}",
+            "version".bright_cyan(),
+            self.version
+        ))?;
+        formatter.write_fmt(format_args!(
+            "\n{}: {}",
+            "data".bright_cyan(),
+            event_kind["data"]
+        ))?;
+        Ok(())
+    }
+}
+
 impl Display for FtTransfer {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         if let Some(memo) = &self.memo {
@@ -1,9 +1,114 @@
 use super::log_view_result;
 use crate::{HumanReadableAccount, HumanReadableFarm};
-use near_contract_standards::non_fungible_token::{Token, TokenId};
-use near_sdk::json_types::U128;
+use contract_lib::{
+    AnnouncedWithdrawal, BurnSimulation, HolderIndex, MintSimulation, MintSnapshot, PauseState,
+    SponsorContribution, StagedUpgrade, TokenInfo,
+};
+use near_contract_standards::{
+    non_fungible_token::{Token, TokenId},
+    storage_management::StorageBalance,
+};
+use near_sdk::json_types::{U128, U64};
 use near_workspaces::{AccountId, Contract};
 
+pub async fn get_accounts(
+    contract: &Contract,
+    from_account: Option<&AccountId>,
+    limit: Option<u32>,
+) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(
+        contract
+            .call("get_accounts")
+            .args_json((from_account, limit))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_holder_count(contract: &Contract) -> anyhow::Result<u32> {
+    let res = log_view_result(contract.call("get_holder_count").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_top_holders(
+    contract: &Contract,
+    from_account: Option<&AccountId>,
+    limit: Option<u32>,
+) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(
+        contract
+            .call("get_top_holders")
+            .args_json((from_account, limit))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_holder_index(contract: &Contract) -> anyhow::Result<HolderIndex> {
+    let res = log_view_result(contract.call("get_holder_index").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_whitelisted_tokens(contract: &Contract) -> anyhow::Result<Vec<AccountId>> {
+    let res = log_view_result(
+        contract
+            .call("get_whitelisted_tokens")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_token(
+    contract: &Contract,
+    token_id: &AccountId,
+) -> anyhow::Result<Option<TokenInfo>> {
+    let res = log_view_result(
+        contract
+            .call("get_token")
+            .args_json((token_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_sponsor_contributions(
+    contract: &Contract,
+    sponsor_id: &AccountId,
+) -> anyhow::Result<Vec<(AccountId, SponsorContribution)>> {
+    let res = log_view_result(
+        contract
+            .call("get_sponsor_contributions")
+            .args_json((sponsor_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_reward_withdrawal_cap(
+    contract: &Contract,
+    token_id: &AccountId,
+) -> anyhow::Result<U128> {
+    let res = log_view_result(
+        contract
+            .call("get_reward_withdrawal_cap")
+            .args_json((token_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
 pub async fn get_undistributed_rewards(
     contract: &Contract,
 ) -> anyhow::Result<Vec<(AccountId, U128)>> {
@@ -17,11 +122,141 @@ pub async fn get_undistributed_rewards(
     Ok(res.json()?)
 }
 
+pub async fn simulate_burn(
+    contract: &Contract,
+    shares: u128,
+) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(
+        contract
+            .call("simulate_burn")
+            .args_json((U128(shares),))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn simulate_mint(contract: &Contract, shares: u128) -> anyhow::Result<MintSimulation> {
+    let res = log_view_result(
+        contract
+            .call("simulate_mint")
+            .args_json((U128(shares),))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn simulate_burn_for(
+    contract: &Contract,
+    account_id: &AccountId,
+    amount: Option<u128>,
+    has_primary_nft: bool,
+) -> anyhow::Result<BurnSimulation> {
+    let res = log_view_result(
+        contract
+            .call("simulate_burn_for")
+            .args_json((account_id, amount.map(U128), has_primary_nft))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_dust(contract: &Contract) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(contract.call("get_dust").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
 pub async fn get_deposits(contract: &Contract) -> anyhow::Result<Vec<(AccountId, U128)>> {
     let res = log_view_result(contract.call("get_deposits").max_gas().view().await?)?;
     Ok(res.json()?)
 }
 
+pub async fn get_pending_payouts(
+    contract: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(
+        contract
+            .call("get_pending_payouts")
+            .args_json((account_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_mint_snapshots(
+    contract: &Contract,
+    from_index: Option<u32>,
+    limit: Option<u32>,
+) -> anyhow::Result<Vec<MintSnapshot>> {
+    let res = log_view_result(
+        contract
+            .call("get_mint_snapshots")
+            .args_json((from_index, limit))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_staged_upgrade(contract: &Contract) -> anyhow::Result<Option<StagedUpgrade>> {
+    let res = log_view_result(contract.call("get_staged_upgrade").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_reward_withdrawals(
+    contract: &Contract,
+) -> anyhow::Result<Vec<(AccountId, AnnouncedWithdrawal)>> {
+    let res = log_view_result(
+        contract
+            .call("get_reward_withdrawals")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_upgrade_delay(contract: &Contract) -> anyhow::Result<U64> {
+    let res = log_view_result(contract.call("get_upgrade_delay").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_state_version(contract: &Contract) -> anyhow::Result<u32> {
+    let res = log_view_result(contract.call("get_state_version").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_owner(contract: &Contract) -> anyhow::Result<AccountId> {
+    let res = log_view_result(contract.call("get_owner").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_pause_state(contract: &Contract) -> anyhow::Result<PauseState> {
+    let res = log_view_result(contract.call("get_pause_state").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn get_roles(contract: &Contract, account_id: &AccountId) -> anyhow::Result<Vec<String>> {
+    let res = log_view_result(
+        contract
+            .call("get_roles")
+            .args_json((account_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
 pub async fn get_farm(contract: &Contract, farm_id: u64) -> anyhow::Result<HumanReadableFarm> {
     let res = log_view_result(
         contract
@@ -77,6 +312,21 @@ pub async fn ft_balance_of(contract: &Contract, account_id: &AccountId) -> anyho
     Ok(res.json()?)
 }
 
+pub async fn storage_balance_of(
+    contract: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<Option<StorageBalance>> {
+    let res = log_view_result(
+        contract
+            .call("storage_balance_of")
+            .args_json((account_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
 pub async fn ft_total_supply(contract: &Contract) -> anyhow::Result<U128> {
     let res = log_view_result(contract.call("ft_total_supply").max_gas().view().await?)?;
     Ok(res.json()?)